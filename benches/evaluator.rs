@@ -0,0 +1,42 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// recursive functions from lib/
+const FIB: &str = include_str!("../lib/fib.cm");
+const FACTORIAL: &str = include_str!("../lib/factorial.cm");
+
+// evaluate program with user-defined functions compiled to bytecode or
+// evaluated from the parsed operations ( the tree walker, not the earlier
+// evaluator that spliced function bodies into the operation list )
+fn evaluate(program: &str, compile: bool) -> comp::Interpreter {
+    let mut comp = comp::Interpreter::new();
+    comp.config.compile = compile;
+    comp.push_ops(program.split_whitespace());
+    comp.evaluate_ops().unwrap();
+
+    comp
+}
+
+fn bench_evaluator(c: &mut Criterion) {
+    let programs: [(&str, String); 3] = [
+        ("fib", format!("{FIB} 18 fib")),
+        ("factorial", format!("{FACTORIAL} 200 factorial")),
+        (
+            "loop",
+            String::from("0 10000 0 do idx 2 % 0 eq if idx + fi loop"),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("evaluator");
+    for (name, program) in programs.iter() {
+        group.bench_with_input(BenchmarkId::new("bytecode", name), program, |b, program| {
+            b.iter(|| evaluate(program, true))
+        });
+        group.bench_with_input(BenchmarkId::new("tree", name), program, |b, program| {
+            b.iter(|| evaluate(program, false))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_evaluator);
+criterion_main!(benches);
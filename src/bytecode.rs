@@ -0,0 +1,174 @@
+use crate::interpreter::Command;
+use crate::lexer::Loc;
+use crate::parser::{Block, Node};
+use crate::value::{Quote, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/*
+
+    user-defined functions are compiled to a flat list of instructions when
+    they are defined. native commands are resolved to their command function
+    and other operations are parsed at compile time ( they are still looked
+    up as user functions and memory when reached, as in the tree walker ).
+    conditionals and loops become jumps to instruction offsets.
+
+      ( fact dup 1 ifeq drop 1 else dup 1 - fact x fi )
+
+        0  Native dup
+        1  Word 1
+        2  Test ifeq -> 6
+        3  Native drop
+        4  Word 1
+        5  Jump -> 11
+        6  Native dup
+        7  Word 1
+        8  Native -
+        9  Word fact
+        10 Native x
+
+*/
+
+/// Compiled operations.
+pub type Code = Arc<[Instr]>;
+
+/// Bytecode instruction.
+#[derive(Debug, Clone)]
+pub enum Instr {
+    Native(Command, String), // native command ( resolved at compile time )
+    Push(Value),             // literal value
+    Word(String, Value),     // user function, memory, or value ( resolved when reached )
+    Define {
+        name: String,
+        fops: Vec<String>,
+        body: Block,
+    }, // function definition
+    Forget(String),          // forget name
+    Test(String, usize),     // pop if / ifeq condition and jump to target if false
+    Jump(usize),             // jump to target
+    Times(usize),            // pop count and start loop ( target is loop exit )
+    Do(usize),               // pop start and limit and start loop ( target is loop exit )
+    Begin(usize, usize),     // start loop ( targets are loop exit and first condition )
+    Loop,                    // next index of times or do loop
+    Until,                   // pop condition and exit loop if true
+    While(usize),            // pop condition and exit loop if false ( target is repeat )
+    Repeat,                  // next iteration of begin loop
+    Line(Loc),               // location of the instructions that follow
+    Bind(Vec<String>),       // pop values into local variables
+    Local(String),           // push value of local variable
+}
+
+/// Compile parsed operations. Commands are resolved using the command map.
+pub fn compile(nodes: &[Node], cmdmap: &HashMap<String, Command>) -> Code {
+    let mut compiler = Compiler {
+        code: vec![],
+        cmdmap,
+    };
+    compiler.block(nodes);
+
+    Code::from(compiler.code)
+}
+
+struct Compiler<'a> {
+    code: Vec<Instr>,
+    cmdmap: &'a HashMap<String, Command>,
+}
+
+impl Compiler<'_> {
+    fn block(&mut self, nodes: &[Node]) {
+        nodes.iter().for_each(|node| self.node(node));
+    }
+
+    fn node(&mut self, node: &Node) {
+        match node {
+            Node::Op(op) => {
+                let instr: Instr = match self.cmdmap.get(op) {
+                    Some(f) => Instr::Native(*f, op.clone()),
+                    None => Instr::Word(op.clone(), Value::parse(op)),
+                };
+                self.code.push(instr);
+            }
+            Node::Quoted(s) => self.code.push(Instr::Push(Value::Str(s.clone()))),
+            Node::Function { name, fops, body } => self.code.push(Instr::Define {
+                name: name.clone(),
+                fops: fops.clone(),
+                body: body.clone(),
+            }),
+            Node::Lambda { fops, body } => self.code.push(Instr::Push(Value::Quote(Arc::new(
+                Quote::new(fops.clone(), body.clone()),
+            )))),
+            Node::Push(value) => self.code.push(Instr::Push(value.clone())),
+            Node::Comment => (),
+            Node::If {
+                op,
+                then,
+                otherwise,
+            } => {
+                let test: usize = self.emit(Instr::Test(op.clone(), 0));
+                self.block(then);
+                match otherwise.is_empty() {
+                    true => self.patch(test),
+                    false => {
+                        let jump: usize = self.emit(Instr::Jump(0));
+                        self.patch(test);
+                        self.block(otherwise);
+                        self.patch(jump);
+                    }
+                }
+            }
+            Node::Until(body) => self.begin(body, None, Instr::Until),
+            Node::While(body, repeat_body) => self.begin(body, Some(repeat_body), Instr::Repeat),
+            Node::Repeat(body) => self.begin(body, None, Instr::Repeat),
+            Node::Times(body) => {
+                let start: usize = self.emit(Instr::Times(0));
+                self.block(body);
+                self.emit(Instr::Loop);
+                self.patch(start);
+            }
+            Node::Do(body) => {
+                let start: usize = self.emit(Instr::Do(0));
+                self.block(body);
+                self.emit(Instr::Loop);
+                self.patch(start);
+            }
+            Node::Forget(name) => self.code.push(Instr::Forget(name.clone())),
+            Node::Line(loc) => self.code.push(Instr::Line(loc.clone())),
+            Node::Bind(names) => self.code.push(Instr::Bind(names.clone())),
+            Node::Local(name) => self.code.push(Instr::Local(name.clone())),
+        }
+    }
+
+    // begin .. end / begin .. while .. repeat
+    fn begin(&mut self, body: &[Node], repeat_body: Option<&Block>, end: Instr) {
+        let start: usize = self.emit(Instr::Begin(0, 0));
+        self.block(body);
+        let check: usize = match repeat_body {
+            Some(repeat_body) => {
+                let check: usize = self.emit(Instr::While(0));
+                self.block(repeat_body);
+                check
+            }
+            None => self.code.len(),
+        };
+        let repeat: usize = self.emit(end);
+        self.code[start] = Instr::Begin(repeat + 1, check);
+        if let Instr::While(target) = &mut self.code[check] {
+            *target = repeat;
+        }
+    }
+
+    // add instruction and return its offset
+    fn emit(&mut self, instr: Instr) -> usize {
+        self.code.push(instr);
+        self.code.len() - 1
+    }
+
+    // point jump instruction at the next offset
+    fn patch(&mut self, offset: usize) {
+        let target: usize = self.code.len();
+        match &mut self.code[offset] {
+            Instr::Test(_, t) | Instr::Jump(t) | Instr::Times(t) | Instr::Do(t) => *t = target,
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompError {
+    StackUnderflow { op: String, min_depth: usize }, // not enough elements on stack
+    Parse { value: String, kind: &'static str },     // value could not be parsed
+    BadArgument { op: String, arg: String },         // argument out of range
+    InvalidArgument { op: String, reason: String },  // argument not usable by command
+    UnbalancedBlock { op: String, close: String },   // block missing closing operator
+    OutsideLoop { op: String },                      // loop control used outside of loop
+    UnknownFile { path: String, reason: String },    // file could not be read
+    Syntax { at: String, reason: String },           // source text could not be split
+    Trace(Box<CompError>, Vec<String>),              // error with location and calls
+}
+
+impl CompError {
+    /// Error without source location and call stack.
+    pub fn cause(&self) -> &CompError {
+        match self {
+            CompError::Trace(error, _) => error.cause(),
+            _ => self,
+        }
+    }
+}
+
+impl std::error::Error for CompError {}
+
+impl CompError {
+    /// Error message with operands and calls formatted by the given functions
+    /// ( e.g. to color them for a terminal ).
+    pub fn styled(
+        &self,
+        operand: &dyn Fn(&str) -> String,
+        call: &dyn Fn(&str) -> String,
+    ) -> String {
+        match self {
+            CompError::StackUnderflow { op, min_depth } => format!(
+                "[{}] operation called without at least {min_depth} element(s) on stack",
+                operand(op),
+            ),
+            CompError::Parse { value, kind } => format!(
+                "unknown expression [{}] is not a recognized operation or valid value ({kind})",
+                operand(value),
+            ),
+            CompError::BadArgument { op, arg } => format!(
+                "[{}] operation called with bad argument [{}]",
+                operand(op),
+                operand(arg),
+            ),
+            CompError::InvalidArgument { op, reason } => format!(
+                "[{}] operation called with invalid argument - {reason}",
+                operand(op),
+            ),
+            CompError::UnbalancedBlock { op, close } => format!(
+                "[{}] block is missing closing [{}]",
+                operand(op),
+                operand(close),
+            ),
+            CompError::OutsideLoop { op } => {
+                format!("[{}] operation called outside of a loop", operand(op))
+            }
+            CompError::Syntax { at, reason } => format!("syntax error at {at} - {reason}"),
+            CompError::UnknownFile { path, reason } => {
+                format!("could not read [{}]: {reason}", operand(path))
+            }
+            CompError::Trace(error, trace) => trace
+                .iter()
+                .fold(error.styled(operand, call), |message, line| {
+                    format!("{message}\n    {}", call(line))
+                }),
+        }
+    }
+}
+
+impl fmt::Display for CompError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plain = |s: &str| s.to_string();
+        f.write_str(&self.styled(&plain, &plain))
+    }
+}
@@ -0,0 +1,6224 @@
+use crate::bytecode::{self, Code, Instr};
+use crate::error::CompError;
+use crate::lexer::{self, Loc, Token};
+use crate::parser::{self, Block, Node};
+use crate::stats;
+use crate::units::Unit;
+use crate::value::{self, Quote, Value};
+use chrono::{DateTime, Datelike, FixedOffset, Local, TimeDelta, Timelike, Utc};
+use num_bigint::{BigInt, BigUint};
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::{env, fmt, fs};
+
+pub(crate) static PERSISTENCE_FILE: &str = ".comp";
+static CONFIG_FILE: &str = "comp.toml";
+static SEARCH_PATH_VAR: &str = "COMP_PATH";
+const SNAPSHOT_VERSION: u32 = 3;
+
+/// Native command function. Receives the interpreter and the name the command
+/// was invoked with.
+pub type Command = fn(&mut Interpreter, &str) -> Result<(), CompError>;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Function {
+    name: String,
+    fops: Vec<String>,
+    #[serde(skip)]
+    body: Block, // parsed function operations
+}
+
+// loop control requested by break or continue
+enum Flow {
+    Break,
+    Continue,
+}
+
+// loop started by compiled code ( offsets are in the code of frame )
+struct LoopFrame {
+    frame: usize, // index of frame running the loop
+    start: usize, // first operation of loop body
+    exit: usize,  // first operation after loop
+    check: usize, // first condition ( or end of body )
+    cont: usize,  // target of continue
+    limit: i64,   // loop index limit
+}
+
+// active user-defined function call
+struct Call {
+    name: String,                   // function name
+    caller: Option<String>,         // calling function ( none if called from outside a function )
+    from: Option<Loc>,              // location of call
+    locals: HashMap<String, Value>, // local variables
+}
+
+// persisted session ( stack, memory, and user-defined functions )
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    version: u32,
+    stack: Vec<Value>,
+    mem: BTreeMap<String, Value>,
+    fns: Vec<Function>,
+}
+
+/// Interpreter configuration ( loaded from `comp.toml` in the home folder ).
+/// Settings missing from the file keep their default values.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub show_stack_level: bool,                    // annotate stack level
+    pub conversion_constant: f64,                  // configurable constant for a_b conversion
+    pub monochrome: bool,                          // set output to monochrome
+    pub tip_percentage: f64,                       // tip conversion constant
+    pub show_warnings: bool,                       // show warnings
+    pub stack_persistence: bool,                   // stack persistence
+    pub prelude: bool,                             // load prelude file at startup
+    pub prelude_file: String,                      // prelude file ( in home folder )
+    pub compile: bool,                             // compile user-defined functions to bytecode
+    pub word_size: u32,                            // programmer mode word size ( 0 is off )
+    pub signed: bool,                              // programmer mode words are signed
+    pub radix: u32,                                // display radix of integers ( 2, 8, 10, or 16 )
+    pub digit_grouping: bool,                      // group digits of displayed integers
+    pub conversions: BTreeMap<String, Conversion>, // named conversions ( registered as commands )
+}
+
+/// Named linear conversion ( `x factor * offset +` ). The conversion and its
+/// inverse are registered as a pair of commands.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Conversion {
+    pub factor: f64,
+    #[serde(default)]
+    pub offset: f64,
+}
+
+impl Conversion {
+    /// Name of the inverse command ( b_a for a conversion named a_b and the
+    /// name followed by _inv otherwise ).
+    pub fn inverse_name(name: &str) -> String {
+        match name.split('_').collect::<Vec<&str>>().as_slice() {
+            [a, b] if !a.is_empty() && !b.is_empty() => format!("{b}_{a}"),
+            _ => format!("{name}_inv"),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    // constructor
+    pub fn new() -> Self {
+        Self {
+            // config defaults
+            show_stack_level: true,
+            conversion_constant: 1.,
+            monochrome: false,
+            tip_percentage: 0.15,
+            show_warnings: true,
+            stack_persistence: false,
+            prelude: true,
+            prelude_file: String::from("prelude.cm"),
+            compile: true,
+            word_size: 0,
+            signed: true,
+            radix: 10,
+            digit_grouping: false,
+            conversions: BTreeMap::new(),
+        }
+    }
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let theme = cor::Theme::new();
+        let fmt = |val: &str| theme.blue_smurf(val);
+        write!(
+            f,
+            "\n\
+            show_stack_level = {}\n\
+            conversion_const = {}\n\
+            monochrome = {}\n\
+            tip_percentage = {}\n\
+            show_warnings = {}\n\
+            stack_persistence = {}\n\
+            prelude = {}\n\
+            prelude_file = {}\n\
+            compile = {}\n\
+            word_size = {}\n\
+            signed = {}\n\
+            radix = {}\n\
+            digit_grouping = {}\n\
+            ",
+            fmt(&self.show_stack_level.to_string()),
+            fmt(&self.conversion_constant.to_string()),
+            fmt(&self.monochrome.to_string()),
+            fmt(&self.tip_percentage.to_string()),
+            fmt(&self.show_warnings.to_string()),
+            fmt(&self.stack_persistence.to_string()),
+            fmt(&self.prelude.to_string()),
+            fmt(&self.prelude_file),
+            fmt(&self.compile.to_string()),
+            fmt(&self.word_size.to_string()),
+            fmt(&self.signed.to_string()),
+            fmt(&self.radix.to_string()),
+            fmt(&self.digit_grouping.to_string()),
+        )?;
+
+        if !self.conversions.is_empty() {
+            writeln!(f, "\n[conversions]")?;
+        }
+        for (name, conversion) in &self.conversions {
+            writeln!(
+                f,
+                "{name} = {{ factor = {}, offset = {} }}  ( {} {} )",
+                fmt(&conversion.factor.to_string()),
+                fmt(&conversion.offset.to_string()),
+                name,
+                Conversion::inverse_name(name),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Stack-based command interpreter.
+///
+/// Operations are queued with [`Interpreter::push_ops`], executed with
+/// [`Interpreter::evaluate_ops`], and the resulting stack is read with
+/// [`Interpreter::stack`].
+pub struct Interpreter {
+    ops: VecDeque<Token>,
+    pub config: Config,
+    stack: Vec<Value>,
+    mem: HashMap<String, Value>,
+    fns: Vec<Function>,
+    code: HashMap<String, Code>,
+    cmdmap: HashMap<String, Command>,
+    theme: cor::Theme,
+    script_dir: Option<PathBuf>,
+    importing: Vec<PathBuf>,
+    imported: Vec<PathBuf>,
+    loops: Vec<i64>,
+    flow: Option<Flow>,
+    location: Option<Loc>,
+    calls: Vec<Call>,
+    prelude_fns: HashMap<String, Vec<String>>,
+    natives: u64,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interpreter {
+    /// Construct an interpreter with the native command set and default
+    /// configuration.
+    pub fn new() -> Self {
+        let mut cint = Self {
+            stack: vec![],
+            mem: HashMap::new(),         // local interpreter memory
+            ops: VecDeque::new(),        // operations list
+            fns: vec![],                 // user-defined functions
+            code: HashMap::new(),        // compiled user-defined functions
+            cmdmap: HashMap::new(),      // interpreter command map
+            config: Config::new(),       // configuration object
+            theme: cor::Theme::new(),    // output format theme
+            script_dir: None,            // directory of script file
+            importing: vec![],           // modules currently being imported
+            imported: vec![],            // modules already imported
+            loops: vec![],               // index of each active loop
+            flow: None,                  // pending break or continue
+            location: None,              // location of current operation
+            calls: vec![],               // active user-defined function calls
+            prelude_fns: HashMap::new(), // functions defined by the prelude
+            natives: 0,                  // command map version
+        };
+        cint.init();
+
+        cint
+    }
+
+    /// Queue an operation ( command or value ) for evaluation.
+    pub fn push_op(&mut self, op: &str) {
+        self.ops.push_back(Token::new(op));
+    }
+
+    /// Queue a list of operations for evaluation.
+    pub fn push_ops<I, S>(&mut self, ops: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.ops
+            .extend(ops.into_iter().map(|op| Token::new(&op.into())));
+    }
+
+    /// Split source text into operations and queue them for evaluation.
+    /// String literals ( "hello world" ) and quoted symbols ( 'drop ) are
+    /// pushed onto the stack without being evaluated. The origin ( file name
+    /// or command line ) is reported in error messages.
+    pub fn push_source(&mut self, source: &str, origin: &str) -> Result<(), CompError> {
+        self.ops.extend(lexer::lex(source, origin)?);
+
+        Ok(())
+    }
+
+    /// Set the path of the script being evaluated. Modules are searched for
+    /// in the script's directory first.
+    pub fn set_script_path(&mut self, path: &Path) {
+        self.script_dir = path.parent().map(Path::to_path_buf);
+    }
+
+    /// Discard all queued operations.
+    pub fn clear_ops(&mut self) {
+        self.ops.clear();
+    }
+
+    /// Evaluate all queued operations in order. On error, the remaining
+    /// operations are left in the queue and the stack is left as the failing
+    /// operation left it ( values it removed are not restored ). Errors are reported with the
+    /// location of the failing operation and the user-defined function calls
+    /// that led to it.
+    pub fn evaluate_ops(&mut self) -> Result<(), CompError> {
+        while self.flow.is_none() {
+            let Some(token) = self.ops.pop_front() else {
+                break;
+            };
+            if token.quoted {
+                self.stack.push(Value::Str(token.op));
+                continue;
+            }
+            self.location = token.loc();
+            if let Err(error) = self.evaluate_op(&token.op) {
+                let error: CompError = self.traced(error);
+                self.calls.clear();
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Register a native command. An existing command with the same name is
+    /// replaced, and user-defined functions are recompiled to call it.
+    pub fn build_native(&mut self, name: &str, func: Command) {
+        self.cmdmap.insert(name.to_string(), func);
+        self.natives += 1; // quotations are compiled again
+
+        for function in &self.fns {
+            self.code.insert(
+                function.name.clone(),
+                bytecode::compile(&function.body, &self.cmdmap),
+            );
+        }
+    }
+
+    fn init(&mut self) {
+        /* stack manipulation */
+        self.build_native("to", Self::c_range); // add range of numbers to stack (generic)
+        self.build_native("cls", Self::c_cls); // clear stack
+        self.build_native("drop", Self::c_drop); // drop element on top of stack
+        self.build_native("dropn", Self::c_dropn); // drop n elements
+        self.build_native("dup", Self::c_dup); // duplicate
+        self.build_native("io", Self::c_iota); // add range of integers to stack (limited - base 1)
+        self.build_native("rev", Self::c_flip); // flip stack order
+        self.build_native("roll", Self::c_roll); // roll stack
+        self.build_native("rolln", Self::c_rolln); // roll stack (n)
+        self.build_native("rot", Self::c_rot); // rotate stack (reverse direction from roll)
+        self.build_native("rotn", Self::c_rotn); // rotate stack (n)
+        self.build_native("swap", Self::c_swap); // swap x and y
+        self.build_native("take", Self::c_take); // take element on top of stack
+        self.build_native("taken", Self::c_taken); // take n elements
+        self.build_native("pack", Self::c_pack); // collect n elements into list
+        self.build_native("unpack", Self::c_unpack); // push elements of list
+
+        /* memory usage */
+        self.build_native("assign", Self::c_assign); // store (pop value off stack and store in generic memory)
+        self.build_native("store", Self::c_assign);
+
+        /* maths operations */
+        self.build_native("+", Self::c_add); // add
+        self.build_native("+_", Self::c_sum); // sum (add all stack elements)
+        self.build_native("sum", Self::c_sum);
+        self.build_native("-", Self::c_sub); // subtract
+        self.build_native("x", Self::c_mult); // multiply
+        self.build_native("x_", Self::c_product); // product (multiply all stack elements)
+        self.build_native("prod", Self::c_product);
+        self.build_native("/", Self::c_div); // divide
+        self.build_native("chs", Self::c_chs); // change sign
+        self.build_native("abs", Self::c_abs); // absolute value
+        self.build_native("round", Self::c_round); // round
+        self.build_native("floor", Self::c_floor); // floor
+        self.build_native("ceil", Self::c_ceiling); // ceiling
+        self.build_native("pos", Self::c_pos);
+        self.build_native("inv", Self::c_inv); // invert (1/x)
+        self.build_native("sqrt", Self::c_sqrt); // square root
+        self.build_native("nroot", Self::c_nroot); // nth root
+        self.build_native("proot", Self::c_proot); // find principal roots
+        self.build_native("^", Self::c_exp); // exponentiation
+        self.build_native("e^", Self::c_natexp); // natural exponential
+        self.build_native("%", Self::c_mod); // modulus
+        self.build_native("float", Self::c_float); // convert to floating point
+        self.build_native("!", Self::c_fact); // factorial
+        self.build_native("gcd", Self::c_gcd); // greatest common divisor
+        self.build_native("pi", Self::c_pi); // pi
+        self.build_native("e", Self::c_euler); // Euler's constant
+        self.build_native("g", Self::c_accelg); // standard acceleration due to gravity (m/s2)
+        self.build_native("deg_rad", Self::c_degrad); // degrees to radians
+        self.build_native("rad_deg", Self::c_raddeg); // radians to degrees
+        self.build_native("sin", Self::c_sin); // sine
+        self.build_native("asin", Self::c_asin); // arcsine
+        self.build_native("cos", Self::c_cos); // cosine
+        self.build_native("acos", Self::c_acos); // arccosine
+        self.build_native("tan", Self::c_tan); // tangent
+        self.build_native("atan", Self::c_atan); // arctangent
+        self.build_native("log2", Self::c_log2); // logarithm (base 2)
+        self.build_native("log", Self::c_log10); // logarithm (base 10)
+        self.build_native("log10", Self::c_log10);
+        self.build_native("logn", Self::c_logn); // logarithm (base n)
+        self.build_native("ln", Self::c_ln); // natural logarithm
+        self.build_native("rand", Self::c_rand); // random number
+        self.build_native("max", Self::c_max); // maximum
+        self.build_native("max_all", Self::c_max_all); // maximum (all)
+        self.build_native("max_", Self::c_max_all);
+        self.build_native("min", Self::c_min); // minimum
+        self.build_native("min_all", Self::c_min_all); // minimum (all)
+        self.build_native("min_", Self::c_min_all);
+        self.build_native("minmax", Self::c_minmax); // minmax
+        self.build_native("avg", Self::c_avg); // average
+        self.build_native("avg_all", Self::c_avg_all); // average (all)
+        self.build_native("avg_", Self::c_avg_all); //
+        self.build_native("sgn", Self::c_sign); // sign function
+        self.build_native("tng", Self::c_triangle); // trianglar numbers function
+        self.build_native("divs", Self::c_divisors); // find divisors of a number
+
+        /* statistics ( whole stack, or top n elements for the n forms ) */
+        self.build_native("median", Self::c_median); // median
+        self.build_native("mediann", Self::c_mediann);
+        self.build_native("mode", Self::c_mode); // most frequent value
+        self.build_native("moden", Self::c_moden);
+        self.build_native("range", Self::c_spread); // difference between maximum and minimum
+        self.build_native("rangen", Self::c_spreadn);
+        self.build_native("var", Self::c_var); // variance (population)
+        self.build_native("varn", Self::c_varn);
+        self.build_native("svar", Self::c_svar); // variance (sample)
+        self.build_native("svarn", Self::c_svarn);
+        self.build_native("stdev", Self::c_stdev); // standard deviation (population)
+        self.build_native("stdevn", Self::c_stdevn);
+        self.build_native("sstdev", Self::c_sstdev); // standard deviation (sample)
+        self.build_native("sstdevn", Self::c_sstdevn);
+        self.build_native("percentile", Self::c_percentile); // percentile
+        self.build_native("percentilen", Self::c_percentilen);
+        self.build_native("quartiles", Self::c_quartiles); // first, second, and third quartiles
+        self.build_native("quartilesn", Self::c_quartilesn);
+        self.build_native("geomean", Self::c_geomean); // geometric mean
+        self.build_native("geomeann", Self::c_geomeann);
+        self.build_native("harmean", Self::c_harmean); // harmonic mean
+        self.build_native("harmeann", Self::c_harmeann);
+        self.build_native("zscore", Self::c_zscore); // standard scores
+        self.build_native("zscoren", Self::c_zscoren);
+        self.build_native("corr", Self::c_corr); // correlation coefficient of x y pairs
+        self.build_native("corrn", Self::c_corrn);
+        self.build_native("linreg", Self::c_linreg); // linear regression ( slope intercept ) of x y pairs
+        self.build_native("linregn", Self::c_linregn);
+
+        /* complex numbers */
+        self.build_native("re", Self::c_real); // real part
+        self.build_native("im", Self::c_imag); // imaginary part
+        self.build_native("conj", Self::c_conj); // complex conjugate
+        self.build_native("arg", Self::c_arg); // argument (phase angle)
+        self.build_native("polar", Self::c_polar); // rectangular to polar ( r theta )
+        self.build_native("rect", Self::c_rect); // polar ( r theta ) to rectangular
+
+        /* control flow */
+        self.build_native("(", Self::c_block); // function definition
+        self.build_native("[", Self::c_block); // anonymous function definition
+        self.build_native("->", Self::c_block); // bind local variables ( -> a b ; )
+        self.build_native("ifeq", Self::c_block); // ifequal .. else
+        self.build_native("if", Self::c_block); // if .. else .. fi
+        self.build_native("begin", Self::c_block); // begin .. until / begin .. while .. repeat
+        self.build_native("times", Self::c_block); // n times .. loop
+        self.build_native("do", Self::c_block); // limit start do .. loop
+        self.build_native("idx", Self::c_index); // loop index
+        self.build_native("break", Self::c_break); // exit loop
+        self.build_native("continue", Self::c_continue); // next loop iteration
+        self.build_native("eq", Self::c_equal); // equal
+        self.build_native("lt", Self::c_lessthan); // less than
+        self.build_native("lte", Self::c_lessthanorequal); // less than or equal
+        self.build_native("gt", Self::c_greaterthan); // greater than
+        self.build_native("gte", Self::c_greaterthanorequal); // greater than or equal
+        self.build_native("{", Self::c_block); // function comment
+
+        /* conversion */
+        self.build_native("dec_hex", Self::c_dechex); // decimal to hexadecimal
+        self.build_native("hex_dec", Self::c_hexdec); // hexadecimal to decimal
+        self.build_native("dec_bin", Self::c_decbin); // decimal to binary
+        self.build_native("bin_dec", Self::c_bindec); // binary to decimal
+        self.build_native("bin_hex", Self::c_binhex); // binary to hexadecimal
+        self.build_native("hex_bin", Self::c_hexbin); // hexadecimal to binary
+        self.build_native("base", Self::c_base); // integer to string in radix ( 2 to 36 )
+        self.build_native("base_dec", Self::c_basedec); // string in radix ( 2 to 36 ) to integer
+        self.build_native("radix", Self::c_radix); // set display radix ( 2, 8, 10, or 16 )
+        self.build_native("c_f", Self::c_celfah); // Celsius to Fahrenheit
+        self.build_native("f_c", Self::c_fahcel); // Fahrenheit to Celsius
+        self.build_native("mi_km", Self::c_mikm); // miles to kilometers
+        self.build_native("km_mi", Self::c_kmmi); // kilometers to miles
+        self.build_native("ft_m", Self::c_ftm); // feet to meters
+        self.build_native("m_ft", Self::c_mft); // meters to feet
+        self.build_native("in", Self::c_in); // convert quantity to unit
+        self.build_native("to_unit", Self::c_in);
+        self.build_native("now", Self::c_now); // current date and time
+        self.build_native("today", Self::c_today); // current date
+        self.build_native("date_unix", Self::c_dateunix); // date to unix timestamp
+        self.build_native("unix_date", Self::c_unixdate); // unix timestamp to date
+        self.build_native("weekday", Self::c_weekday); // day of week ( monday is 1 )
+        self.build_native("week", Self::c_week); // iso week number
+        self.build_native("hms", Self::c_hms); // decimal hours to hours:minutes:seconds
+        self.build_native("hms_dec", Self::c_hmsdec); // hours:minutes:seconds to decimal hours
+        self.build_native("dms", Self::c_dms); // decimal degrees to degrees°minutes'seconds"
+        self.build_native("dms_dec", Self::c_dmsdec); // degrees°minutes'seconds" to decimal degrees
+        self.build_native("hex_rgb", Self::c_hexrgb); // hexadecimal string to RGB
+        self.build_native("rgb_hex", Self::c_rgbhex); // RGB to hexadecimal string
+        self.build_native("tip", Self::c_tip); // calculate tip
+        self.build_native("a_b", Self::c_conv_const); // apply convert constant
+        self.build_native("b_a", Self::c_conv_const_inv); // apply convert constant (inverse)
+        self.build_native("ascii", Self::c_ascii); // ascii table
+
+        /* binary operations */
+        self.build_native("not", Self::c_not); // bitwise not
+        self.build_native("and", Self::c_and); // bitwise and
+        self.build_native("nand", Self::c_nand); // bitwise nand
+        self.build_native("or", Self::c_or); // bitwise or
+        self.build_native("nor", Self::c_nor); // bitwise nor
+        self.build_native("xor", Self::c_xor); // bitwise xor
+        self.build_native("ones", Self::c_ones); // count number of high bits
+        self.build_native("ws", Self::c_word_size); // set word size ( 0 turns programmer mode off )
+        self.build_native("signed", Self::c_signed); // use signed words
+        self.build_native("unsigned", Self::c_unsigned); // use unsigned words
+        self.build_native("shl", Self::c_shl); // shift left
+        self.build_native("shr", Self::c_shr); // shift right ( logical )
+        self.build_native("sar", Self::c_sar); // shift right ( arithmetic )
+        self.build_native("rol", Self::c_rol); // rotate left
+        self.build_native("ror", Self::c_ror); // rotate right
+        self.build_native("bset", Self::c_bset); // set bit
+        self.build_native("bclr", Self::c_bclr); // clear bit
+        self.build_native("btest", Self::c_btest); // test bit
+        self.build_native("clz", Self::c_clz); // count leading zeros
+        self.build_native("ctz", Self::c_ctz); // count trailing zeros
+        self.build_native("bswap", Self::c_bswap); // reverse byte order
+        self.build_native("twos", Self::c_twos); // two's complement bit pattern
+
+        /* RGB colors */
+        self.build_native("rgb", Self::c_rgb); // show RGB color
+        self.build_native("rgbh", Self::c_rgbh); // show RGB color (hexadecimal)
+        self.build_native("rgb_avg", Self::c_rgb_avg); // calculate average RGB color
+        self.build_native("rgbx", Self::c_rgb_mult); // calculate RGB color multiple
+        self.build_native("rgbhx", Self::c_rgbh_mult); // calculate RGB color multiple (hexidecimal)
+
+        /* modules */
+        self.build_native("import", Self::c_import); // import module file
+        self.build_native("use", Self::c_import);
+
+        /* higher-order functions */
+        self.build_native("map", Self::c_map); // map annonymous function to stack
+        self.build_native("fold", Self::c_fold); // fold stack using annonymous function
+        self.build_native("scan", Self::c_scan); // scan stack using annonymous function
+        self.build_native("reduce", Self::c_reduce); // fold list starting from its first element
+        self.build_native("filter", Self::c_filter); // keep elements for which quotation is true
+        self.build_native("zip", Self::c_zip); // combine elements of two lists
+        self.build_native("each", Self::c_each); // call quotation on each list element
+        self.build_native("sort", Self::c_sort); // sort list ( or stack ) by quotation key
+        self.build_native("take_while", Self::c_take_while); // keep leading elements for which quotation is true
+        self.build_native("unique", Self::c_unique); // remove repeated elements
+        self.build_native("group", Self::c_group); // group elements by quotation key
+        self.build_native("count_if", Self::c_count_if); // count elements for which quotation is true
+        self.build_native("find", Self::c_find); // first element for which quotation is true
+        self.build_native("partition", Self::c_partition); // split elements by quotation
+        self.build_native("call", Self::c_call); // call quotation
+        self.build_native("dip", Self::c_dip); // call quotation under top element
+        self.build_native("keep", Self::c_keep); // call quotation and restore its argument
+        self.build_native("bi", Self::c_bi); // call two quotations on the same element
+        self.build_native("compose", Self::c_compose); // join two quotations
+        self.build_native("curry", Self::c_curry); // add element to front of quotation
+
+        /* session */
+        self.build_native("show_mem", Self::c_show_mem); // show memory
+        self.build_native("show_fns", Self::c_show_fns); // show user-defined functions
+        self.build_native("forget", Self::c_block); // forget memory or function ( forget name )
+        self.build_native("reset_mem", Self::c_reset_mem); // clear memory
+        self.build_native("reset_fns", Self::c_reset_fns); // clear user-defined functions
+        self.build_native("reset", Self::c_reset); // clear stack, memory, and functions
+
+        /* configuration */
+        self.build_native("save_config", Self::c_save_config); // save configuration
+        self.build_native("show_config", Self::c_print_config); // show current configuration
+
+        /* output */
+        self.build_native("peek", Self::c_peek); // peek at top of stack
+        self.build_native("print", Self::c_print); // print element on top of stack
+    }
+
+    /// Evaluate a single operation immediately.
+    pub fn evaluate_op(&mut self, op: &str) -> Result<(), CompError> {
+        /* native command? */
+        if self.cmdmap.contains_key(op) {
+            let f = self.cmdmap[op];
+            return f(self, op); // execute command function
+        }
+
+        /* user-defined function? */
+        if let Some(index) = self.is_user_function(op) {
+            // user-defined function - run compiled function or evaluate parsed
+            // function operations
+            let depth: usize = self.calls.len();
+            self.enter(op, false);
+            let result = match self.config.compile {
+                true => self.run_code(self.code[op].clone()),
+                false => self.evaluate_block(self.fns[index].body.clone()),
+            };
+            return self.leave_call(depth, result);
+        }
+
+        /* user memory */
+        if let Some(value) = self.is_user_memory(op) {
+            // user-defined memory - push stored value onto stack
+            self.stack.push(value);
+            return Ok(());
+        }
+
+        /* neither native command nor user-defined function nor user-defined memory */
+
+        // push value onto stack
+        self.stack.push(Value::parse(op));
+
+        Ok(())
+    }
+
+    /* stack access ------------------------------------------------------- */
+
+    /// Current stack ( bottom first ).
+    pub fn stack(&self) -> &[Value] {
+        &self.stack
+    }
+
+    /// Top element of the stack.
+    pub fn peek(&self) -> Option<&Value> {
+        self.stack.last()
+    }
+
+    /// Push a value onto the stack.
+    pub fn push(&mut self, value: Value) {
+        self.stack.push(value);
+    }
+
+    /// Remove and return the top element of the stack.
+    pub fn pop(&mut self) -> Option<Value> {
+        self.stack.pop()
+    }
+
+    /* pop from stack helper functions ( caller confirms stack depth with
+    check_stack_error before popping ) */
+    pub(crate) fn pop_stack_value(&mut self) -> Value {
+        self.stack.pop().unwrap().into_inner()
+    }
+
+    // value as written ( literals keep their text for the commands that read
+    // digits and for commands that only move values )
+    pub(crate) fn pop_stack_literal(&mut self) -> Value {
+        self.stack.pop().unwrap()
+    }
+
+    pub(crate) fn pop_stack_digits(&mut self) -> String {
+        self.pop_stack_literal().digits()
+    }
+
+    pub(crate) fn pop_stack_string(&mut self) -> Result<String, CompError> {
+        Ok(self.pop_stack_value().to_string())
+    }
+
+    pub(crate) fn pop_stack_list(&mut self) -> Result<Vec<Value>, CompError> {
+        self.pop_stack_value().to_list()
+    }
+
+    pub(crate) fn pop_stack_quote(&mut self) -> Result<Arc<Quote>, CompError> {
+        self.pop_stack_value().to_quote()
+    }
+
+    pub(crate) fn pop_stack_date(&mut self) -> Result<DateTime<FixedOffset>, CompError> {
+        self.pop_stack_value().to_date()
+    }
+
+    pub(crate) fn pop_stack_f64(&mut self) -> Result<f64, CompError> {
+        self.pop_stack_value().to_f64()
+    }
+
+    pub(crate) fn pop_stack_i64(&mut self) -> Result<i64, CompError> {
+        self.pop_stack_value().to_int("u")
+    }
+
+    pub(crate) fn pop_stack_u8(&mut self) -> Result<u8, CompError> {
+        self.pop_stack_value().to_int("u")
+    }
+
+    pub(crate) fn pop_stack_usize(&mut self) -> Result<usize, CompError> {
+        self.pop_stack_value().to_int("u")
+    }
+
+    pub(crate) fn pop_stack_u64(&mut self) -> Result<u64, CompError> {
+        self.pop_stack_value().to_int("u")
+    }
+
+    pub(crate) fn pop_stack_biguint(&mut self) -> Result<BigUint, CompError> {
+        self.pop_stack_value().to_int("u")
+    }
+
+    pub(crate) fn pop_stack_int_from_hex(&mut self) -> Result<i64, CompError> {
+        let element: String = self.pop_stack_digits();
+        i64::from_str_radix(&element, 16).map_err(|_| CompError::Parse {
+            value: element,
+            kind: "i_h",
+        })
+    }
+
+    pub(crate) fn pop_stack_u8_from_hex(&mut self) -> Result<u8, CompError> {
+        let element: String = self.pop_stack_digits();
+        u8::from_str_radix(&element, 16).map_err(|_| CompError::Parse {
+            value: element,
+            kind: "i_h",
+        })
+    }
+
+    pub(crate) fn pop_stack_int_from_bin(&mut self) -> Result<i64, CompError> {
+        let element: String = self.pop_stack_digits();
+        i64::from_str_radix(&element, 2).map_err(|_| CompError::Parse {
+            value: element,
+            kind: "i_b",
+        })
+    }
+
+    /// Confirm the stack holds at least `min_depth` elements before
+    /// `command` pops them.
+    pub fn check_stack_error(&self, min_depth: usize, command: &str) -> Result<(), CompError> {
+        if self.stack.len() < min_depth {
+            return Err(CompError::StackUnderflow {
+                op: command.to_string(),
+                min_depth,
+            });
+        }
+
+        Ok(())
+    }
+
+    /* command functions ---------------------------------------------------- */
+
+    /*** command generator helper function ***/
+    fn cmdgen_f64(
+        &mut self,
+        args: usize,
+        op: &str,
+        f: fn(f64, f64) -> f64,
+    ) -> Result<(), CompError> {
+        self.check_stack_error(args, op)?;
+
+        match args {
+            1 => {
+                let a: f64 = self.pop_stack_f64()?;
+                self.stack.push(Value::Float(f(a, 0.)));
+            }
+            2 => {
+                let b: f64 = self.pop_stack_f64()?;
+                let a: f64 = self.pop_stack_f64()?;
+                self.stack.push(Value::Float(f(a, b)));
+            }
+            _ => unimplemented!(),
+        }
+
+        Ok(())
+    }
+
+    fn cmdgen_u64(
+        &mut self,
+        args: usize,
+        op: &str,
+        f: fn(u64, u64) -> u64,
+    ) -> Result<(), CompError> {
+        self.check_stack_error(args, op)?;
+
+        match args {
+            1 => {
+                let a: u64 = self.pop_stack_u64()?;
+                self.stack.push(Value::from(f(a, 0)));
+            }
+            2 => {
+                let b: u64 = self.pop_stack_u64()?;
+                let a: u64 = self.pop_stack_u64()?;
+                self.stack.push(Value::from(f(a, b)));
+            }
+            _ => unimplemented!(),
+        }
+
+        Ok(())
+    }
+
+    // complex operation ( used when an argument is complex )
+    fn cmdgen_c64(
+        &mut self,
+        args: usize,
+        op: &str,
+        f: fn(Complex64, Complex64) -> Complex64,
+    ) -> Result<(), CompError> {
+        self.check_stack_error(args, op)?;
+
+        match args {
+            1 => {
+                let a: Complex64 = self.pop_stack_value().to_c64()?;
+                self.stack.push(Value::from(f(a, Complex64::from(0.))));
+            }
+            2 => {
+                let b: Complex64 = self.pop_stack_value().to_c64()?;
+                let a: Complex64 = self.pop_stack_value().to_c64()?;
+                self.stack.push(Value::from(f(a, b)));
+            }
+            _ => unimplemented!(),
+        }
+
+        Ok(())
+    }
+
+    // is any of the top n stack elements complex?
+    fn has_complex(&self, n: usize) -> bool {
+        self.stack
+            .iter()
+            .rev()
+            .take(n)
+            .any(|a| matches!(a.inner(), Value::Complex(_)))
+    }
+
+    // arbitrary precision unsigned integer operation
+    fn cmdgen_uint(
+        &mut self,
+        args: usize,
+        op: &str,
+        f: fn(BigUint, BigUint) -> BigUint,
+    ) -> Result<(), CompError> {
+        self.check_stack_error(args, op)?;
+
+        match args {
+            1 => {
+                let a: BigUint = self.pop_stack_biguint()?;
+                self.stack.push(Value::from(f(a, BigUint::zero())));
+            }
+            2 => {
+                let b: BigUint = self.pop_stack_biguint()?;
+                let a: BigUint = self.pop_stack_biguint()?;
+                self.stack.push(Value::from(f(a, b)));
+            }
+            _ => unimplemented!(),
+        }
+
+        Ok(())
+    }
+
+    // arbitrary precision signed integer operation ( bitwise operations treat
+    // integers as two's complement with unlimited sign bits )
+    fn cmdgen_sint(
+        &mut self,
+        args: usize,
+        op: &str,
+        f: fn(BigInt, BigInt) -> BigInt,
+    ) -> Result<(), CompError> {
+        self.check_stack_error(args, op)?;
+
+        let b: BigInt = match args {
+            2 => self.pop_stack_value().to_int("i")?,
+            _ => BigInt::zero(),
+        };
+        let a: BigInt = self.pop_stack_value().to_int("i")?;
+        self.stack.push(Value::from(f(a, b)));
+
+        Ok(())
+    }
+
+    // exact ( integer or rational ) operation with floating point fallback ( used
+    // when an argument is a float or the exact operation is not defined )
+    fn cmdgen_num(
+        &mut self,
+        args: usize,
+        op: &str,
+        fi: fn(BigRational, BigRational) -> Option<BigRational>,
+        ff: fn(f64, f64) -> f64,
+    ) -> Result<(), CompError> {
+        self.check_stack_error(args, op)?;
+
+        let (a, b): (Value, Value) = match args {
+            1 => (self.pop_stack_value(), Value::Int(BigInt::zero())),
+            2 => {
+                let b: Value = self.pop_stack_value();
+                (self.pop_stack_value(), b)
+            }
+            _ => unimplemented!(),
+        };
+
+        let exact_result: Option<BigRational> = match (a.as_exact(), b.as_exact()) {
+            (Some(x), Some(y)) => fi(x, y),
+            _ => None,
+        };
+
+        let result: Value = match exact_result {
+            Some(r) => Value::from(r),
+            None => Value::Float(ff(a.to_f64()?, b.to_f64()?)),
+        };
+        self.stack.push(result);
+
+        Ok(())
+    }
+
+    // round rational or float to integer using fr or ff ( integers are unchanged )
+    fn cmdgen_int(
+        &mut self,
+        op: &str,
+        fr: fn(&BigRational) -> BigRational,
+        ff: fn(f64) -> f64,
+    ) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: Value = self.pop_stack_value();
+        let result: Value = match a {
+            Value::Int(_) => a,
+            Value::Rational(r) => Value::from(fr(&r)),
+            _ => Value::from_f64_int(ff(a.to_f64()?)),
+        };
+        self.stack.push(result);
+
+        Ok(())
+    }
+
+    // keep one of two numeric values ( a is kept if f is true for the ordering
+    // of a relative to b )
+    fn cmdgen_select(&mut self, op: &str, f: fn(Ordering) -> bool) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let b: Value = self.pop_stack_value();
+        let a: Value = self.pop_stack_value();
+        match Self::compare(op, &a, &b)?.is_some_and(f) {
+            true => self.stack.push(a),
+            false => self.stack.push(b),
+        }
+
+        Ok(())
+    }
+
+    // compare numeric values ( booleans are returned )
+    fn cmdgen_cmp(&mut self, op: &str, f: fn(Ordering) -> bool) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let b: Value = self.pop_stack_value();
+        let a: Value = self.pop_stack_value();
+        let result: bool = Self::compare(op, &a, &b)?.is_some_and(f);
+        self.stack.push(Value::Bool(result));
+
+        Ok(())
+    }
+
+    // equality of values ( numeric values are compared by value so integer 2
+    // equals float 2.0 and 1 m equals 100 cm )
+    fn equal(a: &Value, b: &Value) -> bool {
+        if let (Value::Quantity(x, x_unit), Value::Quantity(y, y_unit)) = (a, b) {
+            return Self::quantity_cmp(x, x_unit, y, y_unit) == Some(Ordering::Equal);
+        }
+        match (a.as_f64(), b.as_f64()) {
+            (Some(_), Some(_)) => a.num_cmp(b) == Some(Ordering::Equal),
+            _ => a == b,
+        }
+    }
+
+    // numeric ordering of a relative to b ( none if either is not a number )
+    fn compare(op: &str, a: &Value, b: &Value) -> Result<Option<Ordering>, CompError> {
+        if let (Value::Date(x), Value::Date(y)) = (a, b) {
+            return Ok(Some(x.cmp(y)));
+        }
+        if let (Value::Quantity(x, x_unit), Value::Quantity(y, y_unit)) = (a, b) {
+            if !x_unit.compatible(y_unit) {
+                return Err(Self::incompatible_units(op, Some(x_unit), Some(y_unit)));
+            }
+            return Ok(Self::quantity_cmp(x, x_unit, y, y_unit));
+        }
+        a.to_f64()?;
+        b.to_f64()?;
+
+        Ok(a.num_cmp(b))
+    }
+
+    /* ---- stack manipulation ---------------------------------------------- */
+
+    fn c_drop(&mut self, op: &str) -> Result<(), CompError> {
+        if !self.stack.is_empty() {
+            self.stack.pop();
+            return Ok(());
+        }
+
+        // stack empty
+        if self.config.show_warnings {
+            eprintln!(
+                "  {}: [{}] operation called on empty stack",
+                self.theme.yellow_canary_bold("warning"),
+                self.theme.blue_coffee_bold(op),
+            );
+        }
+        // do not stop execution
+
+        Ok(())
+    }
+
+    fn c_dropn(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let mut drop_count: i64 = self.pop_stack_i64()?;
+
+        if drop_count < 1 {
+            return Err(CompError::BadArgument {
+                op: op.to_string(),
+                arg: drop_count.to_string(),
+            });
+        }
+
+        while drop_count > 0 {
+            drop_count -= 1;
+
+            if !self.stack.is_empty() {
+                self.stack.pop();
+                return Ok(());
+            }
+
+            // stack empty
+            if self.config.show_warnings {
+                eprintln!(
+                    "  {}: [{}] operation called on empty stack",
+                    self.theme.yellow_canary_bold("warning"),
+                    self.theme.blue_coffee_bold(op),
+                );
+            }
+            // do not stop execution
+        }
+
+        Ok(())
+    }
+
+    fn c_take(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let keep: Value = self.pop_stack_literal();
+        self.stack = vec![];
+        self.stack.push(keep);
+
+        Ok(())
+    }
+
+    fn c_taken(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let take_count: usize = self.pop_stack_usize()?;
+        let len: usize = self.stack.len();
+
+        if take_count < 1 {
+            return Err(CompError::BadArgument {
+                op: op.to_string(),
+                arg: take_count.to_string(),
+            });
+        }
+
+        if take_count > len {
+            if self.config.show_warnings {
+                eprintln!(
+                    "  {}: [{}] operation called with argument [{}] \
+                    greater than stack depth [{}]",
+                    self.theme.yellow_canary_bold("warning"),
+                    self.theme.blue_coffee_bold(op),
+                    self.theme.blue_coffee_bold(&take_count.to_string()),
+                    self.theme.blue_coffee_bold(&len.to_string()),
+                );
+            }
+            return Ok(());
+        }
+
+        self.stack = self.stack[(len - take_count)..len].to_vec();
+
+        Ok(())
+    }
+
+    fn c_dup(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        self.stack.push(self.stack[self.stack.len() - 1].clone()); // remove last
+
+        Ok(())
+    }
+
+    fn c_swap(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let end: usize = self.stack.len() - 1;
+
+        self.stack.swap(end, end - 1);
+
+        Ok(())
+    }
+
+    fn c_cls(&mut self, _op: &str) -> Result<(), CompError> {
+        self.stack.clear();
+
+        Ok(())
+    }
+
+    fn c_roll(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        self.stack.rotate_right(1);
+
+        Ok(())
+    }
+
+    fn c_rolln(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let a: usize = self.pop_stack_usize()?;
+
+        self.stack.rotate_right(a);
+
+        Ok(())
+    }
+
+    fn c_rot(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        self.stack.rotate_left(1);
+
+        Ok(())
+    }
+
+    fn c_rotn(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let a: usize = self.pop_stack_usize()?;
+
+        self.stack.rotate_left(a);
+
+        Ok(())
+    }
+
+    fn c_range(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(3, op)?;
+
+        let step: Value = self.pop_stack_value();
+        let end: Value = self.pop_stack_value();
+        let start: Value = self.pop_stack_value();
+
+        // integer range if all arguments are integers
+        if let (Some(start), Some(end), Some(step)) = (
+            start.as_exact_int(),
+            end.as_exact_int(),
+            step.as_exact_int(),
+        ) {
+            let step: BigInt = step.abs().max(BigInt::from(1));
+            let mut value: BigInt = start.clone();
+            if end >= start {
+                while value <= end {
+                    self.stack.push(Value::Int(value.clone()));
+                    value += &step;
+                }
+            } else {
+                while value >= end {
+                    self.stack.push(Value::Int(value.clone()));
+                    value -= &step;
+                }
+            }
+            return Ok(());
+        }
+
+        let step: f64 = step.to_f64()?;
+        let end: f64 = end.to_f64()?;
+        let start: f64 = start.to_f64()?;
+
+        let mut value: f64 = start;
+        if end >= start {
+            while value <= end {
+                self.stack.push(Value::Float(value));
+                value += step.abs();
+            }
+        } else {
+            while value >= end {
+                self.stack.push(Value::Float(value));
+                value -= step.abs();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn c_iota(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: i64 = self.pop_stack_i64()?;
+
+        if a < 1 {
+            return Err(CompError::InvalidArgument {
+                op: op.to_string(),
+                reason: String::from("argument cannot be less than 1"),
+            });
+        }
+
+        for i in 1..=a {
+            self.stack.push(Value::from(i));
+        }
+
+        Ok(())
+    }
+
+    fn c_flip(&mut self, _op: &str) -> Result<(), CompError> {
+        self.stack = self.stack.clone().into_iter().rev().collect();
+
+        Ok(())
+    }
+
+    fn c_pack(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let count: usize = self.pop_stack_usize()?;
+        self.check_stack_error(count, op)?;
+
+        let list: Vec<Value> = self.stack.split_off(self.stack.len() - count);
+        self.stack.push(Value::List(list));
+
+        Ok(())
+    }
+
+    fn c_unpack(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let list: Vec<Value> = self.pop_stack_list()?;
+        self.stack.extend(list);
+
+        Ok(())
+    }
+
+    /* ---- memory usage ---------------------------------------------------- */
+
+    fn c_assign(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let key = self.pop_stack_string()?;
+        let val = self.pop_stack_literal();
+
+        self.mem.insert(key, val);
+
+        Ok(())
+    }
+
+    /* ---- math operations ------------------------------------------------- */
+
+    fn c_add(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_date(2) {
+            return self.cmdgen_date(op, 1);
+        }
+        if self.has_quantity(2) {
+            return self.cmdgen_sum_units(op, Self::c_add);
+        }
+        if self.has_complex(2) {
+            return self.cmdgen_c64(2, op, |a, b| a + b);
+        }
+        self.cmdgen_num(2, op, |a, b| Some(a + b), |a, b| a + b)
+    }
+
+    fn c_sum(&mut self, op: &str) -> Result<(), CompError> {
+        while self.stack.len() > 1 {
+            self.c_add(op)?;
+        }
+
+        Ok(())
+    }
+
+    fn c_sub(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_date(2) {
+            return self.cmdgen_date(op, -1);
+        }
+        if self.has_quantity(2) {
+            return self.cmdgen_sum_units(op, Self::c_sub);
+        }
+        if self.has_complex(2) {
+            return self.cmdgen_c64(2, op, |a, b| a - b);
+        }
+        self.cmdgen_num(2, op, |a, b| Some(a - b), |a, b| a - b)
+    }
+
+    fn c_mult(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_quantity(2) {
+            return self.cmdgen_product_units(op, Self::c_mult, 1);
+        }
+        if self.has_complex(2) {
+            return self.cmdgen_c64(2, op, |a, b| a * b);
+        }
+        self.cmdgen_num(2, op, |a, b| Some(a * b), |a, b| a * b)
+    }
+
+    fn c_product(&mut self, op: &str) -> Result<(), CompError> {
+        while self.stack.len() > 1 {
+            self.c_mult(op)?;
+        }
+
+        Ok(())
+    }
+
+    fn c_div(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_quantity(2) {
+            return self.cmdgen_product_units(op, Self::c_div, -1);
+        }
+        if self.has_complex(2) {
+            return self.cmdgen_c64(2, op, |a, b| a / b);
+        }
+        let fi = |a: BigRational, b: BigRational| match b.is_zero() {
+            true => None,
+            false => Some(a / b),
+        };
+        self.cmdgen_num(2, op, fi, |a, b| a / b)
+    }
+
+    fn c_chs(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_quantity(1) {
+            return self.cmdgen_unary_units(op, Self::c_chs, |unit| Some(unit.clone()));
+        }
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| -a);
+        }
+        self.cmdgen_num(1, op, |a, _| Some(-a), |a, _| -a)
+    }
+
+    fn c_abs(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_quantity(1) {
+            return self.cmdgen_unary_units(op, Self::c_abs, |unit| Some(unit.clone()));
+        }
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| Complex64::from(a.norm()));
+        }
+        self.cmdgen_num(1, op, |a, _| Some(a.abs()), |a, _| a.abs())
+    }
+
+    fn c_round(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_int(op, BigRational::round, f64::round)
+    }
+
+    fn c_floor(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_int(op, BigRational::floor, f64::floor)
+    }
+
+    fn c_ceiling(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_int(op, BigRational::ceil, f64::ceil)
+    }
+
+    fn c_pos(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_num(
+            1,
+            op,
+            |a, _| Some(a.max(BigRational::zero())),
+            |a, _| if a < 0. { 0. } else { a },
+        )
+    }
+
+    fn c_inv(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_quantity(1) {
+            return self.cmdgen_unary_units(op, Self::c_inv, |unit| Some(unit.pow(-1)));
+        }
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| a.inv());
+        }
+        let fi = |a: BigRational, _| match a.is_zero() {
+            true => None,
+            false => Some(a.recip()),
+        };
+        self.cmdgen_num(1, op, fi, |a, _| 1. / a)
+    }
+
+    fn c_sqrt(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_quantity(1) {
+            return self.cmdgen_unary_units(op, Self::c_sqrt, |unit| unit.root(2));
+        }
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| a.sqrt());
+        }
+        self.cmdgen_f64(1, op, |a, _| a.sqrt())
+    }
+
+    fn c_nroot(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_f64(2, op, |a, b| a.powf(1. / b))
+    }
+
+    fn c_proot(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(3, op)?;
+
+        let c: Complex64 = self.pop_stack_value().to_c64()?;
+        let b: Complex64 = self.pop_stack_value().to_c64()?;
+        let a: Complex64 = self.pop_stack_value().to_c64()?;
+
+        let disc: Complex64 = b * b - 4. * a * c; // discriminant
+        self.stack.push(Value::from((-b + disc.sqrt()) / (2. * a))); // r_1
+        self.stack.push(Value::from((-b - disc.sqrt()) / (2. * a))); // r_2
+
+        Ok(())
+    }
+
+    fn c_exp(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_quantity(2) {
+            return self.cmdgen_power_units(op);
+        }
+        if self.has_complex(2) {
+            return self.cmdgen_c64(2, op, |a, b| a.powc(b));
+        }
+
+        // exact for integer exponents
+        let fi = |a: BigRational, b: BigRational| {
+            let n: i32 = match b.is_integer() {
+                true => i32::try_from(b.to_integer()).ok()?,
+                false => return None,
+            };
+            match a.is_zero() && n < 0 {
+                true => None,
+                false => Some(a.pow(n)),
+            }
+        };
+        self.cmdgen_num(2, op, fi, |a, b| a.powf(b))
+    }
+
+    fn c_natexp(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| a.exp());
+        }
+        self.cmdgen_f64(1, op, |a, _| a.exp())
+    }
+
+    fn c_mod(&mut self, op: &str) -> Result<(), CompError> {
+        let fi = |a: BigRational, b: BigRational| match b.is_zero() {
+            true => None,
+            false => Some(a % b),
+        };
+        self.cmdgen_num(2, op, fi, |a, b| a % b)
+    }
+
+    fn c_float(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_f64(1, op, |a, _| a)
+    }
+
+    fn c_fact(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: u64 = self.pop_stack_u64()?;
+
+        self.stack.push(Value::from(Self::factorial(a)));
+
+        Ok(())
+    }
+
+    fn c_gcd(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_uint(2, op, Self::gcd)
+    }
+
+    fn c_pi(&mut self, _op: &str) -> Result<(), CompError> {
+        self.stack.push(Value::Float(std::f64::consts::PI));
+
+        Ok(())
+    }
+
+    fn c_euler(&mut self, _op: &str) -> Result<(), CompError> {
+        self.stack.push(Value::Float(std::f64::consts::E));
+
+        Ok(())
+    }
+
+    fn c_accelg(&mut self, _op: &str) -> Result<(), CompError> {
+        self.stack.push(Value::Float(9.80665));
+
+        Ok(())
+    }
+
+    fn c_degrad(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_f64(1, op, |a, _| a.to_radians())
+    }
+
+    fn c_raddeg(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_f64(1, op, |a, _| a.to_degrees())
+    }
+
+    fn c_sin(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| a.sin());
+        }
+        self.cmdgen_f64(1, op, |a, _| a.sin())
+    }
+
+    fn c_asin(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| a.asin());
+        }
+        self.cmdgen_f64(1, op, |a, _| a.asin())
+    }
+
+    fn c_cos(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| a.cos());
+        }
+        self.cmdgen_f64(1, op, |a, _| a.cos())
+    }
+
+    fn c_acos(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| a.acos());
+        }
+        self.cmdgen_f64(1, op, |a, _| a.acos())
+    }
+
+    fn c_tan(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| a.tan());
+        }
+        self.cmdgen_f64(1, op, |a, _| a.tan())
+    }
+
+    fn c_atan(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| a.atan());
+        }
+        self.cmdgen_f64(1, op, |a, _| a.atan())
+    }
+
+    fn c_log10(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_f64(1, op, |a, _| a.log10())
+    }
+
+    fn c_log2(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_f64(1, op, |a, _| a.log2())
+    }
+
+    fn c_logn(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_f64(2, op, |a, b| a.log(b))
+    }
+
+    fn c_ln(&mut self, op: &str) -> Result<(), CompError> {
+        if self.has_complex(1) {
+            return self.cmdgen_c64(1, op, |a, _| a.ln());
+        }
+        self.cmdgen_f64(1, op, |a, _| a.ln())
+    }
+
+    fn c_rand(&mut self, op: &str) -> Result<(), CompError> {
+        let f = |a, _| (a as f64 * rand::random::<f64>() + 1.) as u64;
+        self.cmdgen_u64(1, op, f)
+    }
+
+    fn c_max(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_select(op, Ordering::is_ge)
+    }
+
+    fn c_max_all(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        while self.stack.len() > 1 {
+            self.c_max(op)?;
+        }
+
+        Ok(())
+    }
+
+    fn c_min(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_select(op, Ordering::is_le)
+    }
+
+    fn c_min_all(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        while self.stack.len() > 1 {
+            self.c_min(op)?;
+        }
+
+        Ok(())
+    }
+
+    fn c_minmax(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let mut max: Value = self.pop_stack_value();
+        let mut min: Value = max.clone();
+        while !self.stack.is_empty() {
+            let a: Value = self.pop_stack_value();
+
+            if Self::compare(op, &a, &max)? == Some(Ordering::Greater) {
+                max = a
+            } else if Self::compare(op, &a, &min)? == Some(Ordering::Less) {
+                min = a
+            }
+        }
+
+        self.stack.push(min);
+        self.stack.push(max);
+
+        Ok(())
+    }
+
+    fn c_avg(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        self.c_add(op)?;
+        self.stack.push(Value::from(2i64));
+        self.c_div(op)
+    }
+
+    fn c_avg_all(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let len: usize = self.stack.len();
+        self.c_sum(op)?;
+        self.stack.push(Value::from(len as u64));
+        self.c_div(op)?;
+
+        Ok(())
+    }
+
+    fn c_sign(&mut self, op: &str) -> Result<(), CompError> {
+        fn sgn(a: f64) -> f64 {
+            match a {
+                x if x < 0. => -1.,
+                x if x > 0. => 1.,
+                _ => 0.,
+            }
+        }
+
+        self.cmdgen_num(1, op, |a, _| Some(a.signum()), |a, _| sgn(a))
+    }
+
+    fn c_triangle(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_uint(1, op, |a, _| &a * (&a + 1u32) / 2u32)
+    }
+
+    fn c_divisors(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: i64 = self.pop_stack_i64()?.abs();
+
+        let mut divisors: Vec<i64> = vec![1];
+        let sq: i64 = (a as f64).sqrt() as i64;
+
+        (2..=sq).for_each(|n| {
+            if a % n == 0 {
+                divisors.push(n);
+                if n != sq {
+                    divisors.push(a / n)
+                }
+            }
+        });
+
+        divisors.sort_unstable();
+
+        divisors
+            .into_iter()
+            .for_each(|n| self.stack.push(Value::from(n)));
+
+        Ok(())
+    }
+
+    /* ---- statistics ------------------------------------------------------ */
+
+    // samples of statistics command ( the whole stack or, for the top-n form
+    // of the command, the n elements below n )
+    fn pop_samples(&mut self, op: &str, top: bool, min: usize) -> Result<Vec<Value>, CompError> {
+        if !top {
+            self.check_stack_error(min, op)?;
+            return Ok(std::mem::take(&mut self.stack));
+        }
+
+        self.check_stack_error(1, op)?;
+        let n: usize = self.pop_stack_usize()?;
+        if n < min {
+            return Err(CompError::BadArgument {
+                op: op.to_string(),
+                arg: n.to_string(),
+            });
+        }
+        self.check_stack_error(n, op)?;
+
+        Ok(self.stack.split_off(self.stack.len() - n))
+    }
+
+    // samples as floating point values ( error if a sample is not a real
+    // number )
+    fn pop_samples_f64(&mut self, op: &str, top: bool, min: usize) -> Result<Vec<f64>, CompError> {
+        self.pop_samples(op, top, min)?
+            .iter()
+            .map(Value::to_f64)
+            .collect()
+    }
+
+    // x y pairs ( the top-n form counts pairs )
+    fn pop_pairs(&mut self, op: &str, top: bool) -> Result<(Vec<f64>, Vec<f64>), CompError> {
+        let samples: Vec<f64> = match top {
+            true => {
+                self.check_stack_error(1, op)?;
+                let n: usize = self.pop_stack_usize()?;
+                if n < 2 {
+                    return Err(CompError::BadArgument {
+                        op: op.to_string(),
+                        arg: n.to_string(),
+                    });
+                }
+                self.stack.push(Value::from(2 * n as u64));
+                self.pop_samples_f64(op, true, 4)?
+            }
+            false => {
+                if self.stack.len() % 2 == 1 {
+                    return Err(CompError::InvalidArgument {
+                        op: op.to_string(),
+                        reason: String::from("stack does not hold x y pairs"),
+                    });
+                }
+                self.pop_samples_f64(op, false, 4)?
+            }
+        };
+
+        Ok(samples.chunks(2).map(|pair| (pair[0], pair[1])).unzip())
+    }
+
+    // samples in ascending order
+    fn sort_samples(samples: &mut [Value]) -> Result<(), CompError> {
+        for x in samples.iter() {
+            x.to_f64()?;
+        }
+        samples.sort_by(|a, b| a.num_cmp(b).unwrap_or(Ordering::Equal));
+
+        Ok(())
+    }
+
+    fn no_variation(op: &str) -> CompError {
+        CompError::InvalidArgument {
+            op: op.to_string(),
+            reason: String::from("values do not vary"),
+        }
+    }
+
+    fn c_median(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_median(op, false)
+    }
+
+    fn c_mediann(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_median(op, true)
+    }
+
+    // middle value ( average of the two middle values of an even number of
+    // samples )
+    fn stat_median(&mut self, op: &str, top: bool) -> Result<(), CompError> {
+        let mut samples: Vec<Value> = self.pop_samples(op, top, 1)?;
+        Self::sort_samples(&mut samples)?;
+
+        let mid: usize = samples.len() / 2;
+        if samples.len() % 2 == 1 {
+            self.stack.push(samples.swap_remove(mid));
+            return Ok(());
+        }
+        self.stack.extend(samples.drain(mid - 1..=mid));
+        self.c_avg(op)
+    }
+
+    fn c_mode(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_mode(op, false)
+    }
+
+    fn c_moden(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_mode(op, true)
+    }
+
+    // most frequent value ( the smallest of equally frequent values )
+    fn stat_mode(&mut self, op: &str, top: bool) -> Result<(), CompError> {
+        let mut samples: Vec<Value> = self.pop_samples(op, top, 1)?;
+        Self::sort_samples(&mut samples)?;
+
+        let (mut mode, mut most): (usize, usize) = (0, 0);
+        let mut start: usize = 0;
+        for i in 1..=samples.len() {
+            if i == samples.len() || !Self::equal(&samples[i], &samples[start]) {
+                if i - start > most {
+                    (mode, most) = (start, i - start);
+                }
+                start = i;
+            }
+        }
+        self.stack.push(samples.swap_remove(mode));
+
+        Ok(())
+    }
+
+    fn c_spread(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_range(op, false)
+    }
+
+    fn c_spreadn(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_range(op, true)
+    }
+
+    // difference between largest and smallest values
+    fn stat_range(&mut self, op: &str, top: bool) -> Result<(), CompError> {
+        let mut samples: Vec<Value> = self.pop_samples(op, top, 1)?;
+        Self::sort_samples(&mut samples)?;
+
+        let min: Value = samples.remove(0);
+        self.stack
+            .push(samples.pop().unwrap_or_else(|| min.clone()));
+        self.stack.push(min);
+        self.c_sub(op)
+    }
+
+    fn c_var(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_variance(op, false, false)
+    }
+
+    fn c_varn(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_variance(op, true, false)
+    }
+
+    fn c_svar(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_variance(op, false, true)
+    }
+
+    fn c_svarn(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_variance(op, true, true)
+    }
+
+    // population ( or sample ) variance
+    fn stat_variance(&mut self, op: &str, top: bool, sample: bool) -> Result<(), CompError> {
+        let samples: Vec<f64> = self.pop_samples_f64(op, top, 1 + sample as usize)?;
+        self.stack
+            .push(Value::Float(stats::variance(&samples, sample)));
+
+        Ok(())
+    }
+
+    fn c_stdev(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_variance(op, false, false)?;
+        self.c_sqrt(op)
+    }
+
+    fn c_stdevn(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_variance(op, true, false)?;
+        self.c_sqrt(op)
+    }
+
+    fn c_sstdev(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_variance(op, false, true)?;
+        self.c_sqrt(op)
+    }
+
+    fn c_sstdevn(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_variance(op, true, true)?;
+        self.c_sqrt(op)
+    }
+
+    fn c_percentile(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_percentile(op, false)
+    }
+
+    fn c_percentilen(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_percentile(op, true)
+    }
+
+    // percentile ( the percentage is on top of the stack, above n for the
+    // top-n form )
+    fn stat_percentile(&mut self, op: &str, top: bool) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let p: f64 = self.pop_stack_f64()?;
+        if !(0. ..=100.).contains(&p) {
+            return Err(CompError::BadArgument {
+                op: op.to_string(),
+                arg: p.to_string(),
+            });
+        }
+
+        let mut samples: Vec<f64> = self.pop_samples_f64(op, top, 1)?;
+        samples.sort_by(f64::total_cmp);
+        self.stack
+            .push(Value::Float(stats::percentile(&samples, p)));
+
+        Ok(())
+    }
+
+    fn c_quartiles(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_quartiles(op, false)
+    }
+
+    fn c_quartilesn(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_quartiles(op, true)
+    }
+
+    // first, second, and third quartiles
+    fn stat_quartiles(&mut self, op: &str, top: bool) -> Result<(), CompError> {
+        let mut samples: Vec<f64> = self.pop_samples_f64(op, top, 1)?;
+        samples.sort_by(f64::total_cmp);
+        for p in [25., 50., 75.] {
+            self.stack
+                .push(Value::Float(stats::percentile(&samples, p)));
+        }
+
+        Ok(())
+    }
+
+    fn c_geomean(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_mean(op, false, stats::geomean)
+    }
+
+    fn c_geomeann(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_mean(op, true, stats::geomean)
+    }
+
+    fn c_harmean(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_mean(op, false, stats::harmean)
+    }
+
+    fn c_harmeann(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_mean(op, true, stats::harmean)
+    }
+
+    // geometric or harmonic mean ( of positive values )
+    fn stat_mean(&mut self, op: &str, top: bool, f: fn(&[f64]) -> f64) -> Result<(), CompError> {
+        let samples: Vec<f64> = self.pop_samples_f64(op, top, 1)?;
+        if let Some(x) = samples.iter().find(|x| **x <= 0.) {
+            return Err(CompError::BadArgument {
+                op: op.to_string(),
+                arg: x.to_string(),
+            });
+        }
+        self.stack.push(Value::Float(f(&samples)));
+
+        Ok(())
+    }
+
+    fn c_zscore(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_zscore(op, false)
+    }
+
+    fn c_zscoren(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_zscore(op, true)
+    }
+
+    // standard scores of the values ( deviations from the mean in population
+    // standard deviations )
+    fn stat_zscore(&mut self, op: &str, top: bool) -> Result<(), CompError> {
+        let samples: Vec<f64> = self.pop_samples_f64(op, top, 2)?;
+
+        let mean: f64 = stats::mean(&samples);
+        let stdev: f64 = stats::variance(&samples, false).sqrt();
+        if stdev == 0. {
+            return Err(Self::no_variation(op));
+        }
+        self.stack
+            .extend(samples.iter().map(|x| Value::Float((x - mean) / stdev)));
+
+        Ok(())
+    }
+
+    fn c_corr(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_corr(op, false)
+    }
+
+    fn c_corrn(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_corr(op, true)
+    }
+
+    // correlation coefficient of x y pairs
+    fn stat_corr(&mut self, op: &str, top: bool) -> Result<(), CompError> {
+        let (xs, ys) = self.pop_pairs(op, top)?;
+
+        let r: f64 = stats::corr(&xs, &ys);
+        if r.is_nan() {
+            return Err(Self::no_variation(op));
+        }
+        self.stack.push(Value::Float(r));
+
+        Ok(())
+    }
+
+    fn c_linreg(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_linreg(op, false)
+    }
+
+    fn c_linregn(&mut self, op: &str) -> Result<(), CompError> {
+        self.stat_linreg(op, true)
+    }
+
+    // slope and intercept of the least squares line through x y pairs
+    fn stat_linreg(&mut self, op: &str, top: bool) -> Result<(), CompError> {
+        let (xs, ys) = self.pop_pairs(op, top)?;
+
+        let (slope, intercept) = stats::linreg(&xs, &ys);
+        if !slope.is_finite() {
+            return Err(Self::no_variation(op));
+        }
+        self.stack.push(Value::Float(slope));
+        self.stack.push(Value::Float(intercept));
+
+        Ok(())
+    }
+
+    /* ---- complex numbers ------------------------------------------------- */
+
+    fn c_real(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_c64(1, op, |a, _| Complex64::from(a.re))
+    }
+
+    fn c_imag(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_c64(1, op, |a, _| Complex64::from(a.im))
+    }
+
+    fn c_conj(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_c64(1, op, |a, _| a.conj())
+    }
+
+    fn c_arg(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_c64(1, op, |a, _| Complex64::from(a.arg()))
+    }
+
+    fn c_polar(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: Complex64 = self.pop_stack_value().to_c64()?;
+        let (r, theta) = a.to_polar();
+
+        self.stack.push(Value::Float(r));
+        self.stack.push(Value::Float(theta));
+
+        Ok(())
+    }
+
+    fn c_rect(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let theta: f64 = self.pop_stack_f64()?;
+        let r: f64 = self.pop_stack_f64()?;
+
+        self.stack
+            .push(Value::from(Complex64::from_polar(r, theta)));
+
+        Ok(())
+    }
+
+    /* ---- conversions ----------------------------------------------------- */
+
+    fn c_dechex(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: u64 = self.pop_stack_u64()?;
+
+        self.stack.push(Value::Str(format!("{:x}", a)));
+
+        Ok(())
+    }
+
+    fn c_hexdec(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a = self.pop_stack_int_from_hex()?;
+
+        self.stack.push(Value::from(a));
+
+        Ok(())
+    }
+
+    fn c_decbin(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: u64 = self.pop_stack_u64()?;
+
+        self.stack.push(Value::Str(format!("{:b}", a)));
+
+        Ok(())
+    }
+
+    fn c_bindec(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a = self.pop_stack_int_from_bin()?;
+
+        self.stack.push(Value::from(a));
+
+        Ok(())
+    }
+
+    fn c_binhex(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a = self.pop_stack_int_from_bin()?;
+
+        self.stack.push(Value::Str(format!("{:x}", a)));
+
+        Ok(())
+    }
+
+    fn c_hexbin(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a = self.pop_stack_int_from_hex()?;
+
+        self.stack.push(Value::Str(format!("{:b}", a)));
+
+        Ok(())
+    }
+
+    // radix of base conversion ( error if not 2 to 36 )
+    fn pop_stack_radix(&mut self, op: &str) -> Result<u32, CompError> {
+        let radix: u32 = self.pop_stack_value().to_int("u")?;
+        if !(2..=36).contains(&radix) {
+            return Err(CompError::BadArgument {
+                op: op.to_string(),
+                arg: radix.to_string(),
+            });
+        }
+
+        Ok(radix)
+    }
+
+    // integer radix base -> digits of integer in radix
+    fn c_base(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let radix: u32 = self.pop_stack_radix(op)?;
+        let a: BigInt = self.pop_stack_value().to_int("i")?;
+        self.stack.push(Value::Str(a.to_str_radix(radix)));
+
+        Ok(())
+    }
+
+    // digits radix base_dec -> integer
+    fn c_basedec(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let radix: u32 = self.pop_stack_radix(op)?;
+        let digits: String = self.pop_stack_digits();
+        let a: BigInt =
+            BigInt::parse_bytes(digits.as_bytes(), radix).ok_or_else(|| CompError::Parse {
+                value: digits.clone(),
+                kind: "i_r",
+            })?;
+        self.stack.push(Value::from(a));
+
+        Ok(())
+    }
+
+    fn c_radix(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let radix: u32 = self.pop_stack_value().to_int("u")?;
+        if ![2, 8, 10, 16].contains(&radix) {
+            return Err(CompError::BadArgument {
+                op: op.to_string(),
+                arg: radix.to_string(),
+            });
+        }
+        self.config.radix = radix;
+
+        Ok(())
+    }
+
+    fn c_celfah(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_convert(op, "degC", "degF")
+    }
+
+    fn c_fahcel(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_convert(op, "degF", "degC")
+    }
+
+    fn c_mikm(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_convert(op, "mi", "km")
+    }
+
+    fn c_kmmi(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_convert(op, "km", "mi")
+    }
+
+    fn c_ftm(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_convert(op, "ft", "m")
+    }
+
+    fn c_mft(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_convert(op, "m", "ft")
+    }
+
+    fn c_hexrgb(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let she: String = self.pop_stack_digits();
+
+        if she.len() < 5 {
+            return Err(CompError::InvalidArgument {
+                op: op.to_string(),
+                reason: format!("argument [{she}] is not of sufficient length"),
+            });
+        }
+
+        let parse_hex = |sh: &str| {
+            i64::from_str_radix(sh, 16).map_err(|_| CompError::Parse {
+                value: she.clone(),
+                kind: "i_h",
+            })
+        };
+
+        let r: i64 = parse_hex(&she[..2])?;
+        let g: i64 = parse_hex(&she[2..4])?;
+        let b: i64 = parse_hex(&she[4..])?;
+
+        self.stack.push(Value::from(r));
+        self.stack.push(Value::from(g));
+        self.stack.push(Value::from(b));
+
+        Ok(())
+    }
+
+    fn c_rgbhex(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(3, op)?;
+
+        let b: u64 = self.pop_stack_u64()?;
+        let g: u64 = self.pop_stack_u64()?;
+        let r: u64 = self.pop_stack_u64()?;
+
+        self.stack
+            .push(Value::Str(format!("{:02x}{:02x}{:02x}", r, g, b)));
+
+        Ok(())
+    }
+
+    fn c_tip(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: f64 = self.pop_stack_f64()?;
+
+        self.stack
+            .push(Value::Float(a * self.config.tip_percentage));
+
+        Ok(())
+    }
+
+    fn c_conv_const(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: f64 = self.pop_stack_f64()?;
+
+        self.stack
+            .push(Value::Float(a * self.config.conversion_constant));
+
+        Ok(())
+    }
+
+    fn c_conv_const_inv(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: f64 = self.pop_stack_f64()?;
+
+        self.stack
+            .push(Value::Float(a / self.config.conversion_constant));
+
+        Ok(())
+    }
+
+    // register the conversions of the configuration and their inverses as
+    // commands ( conversions named after other commands are ignored )
+    fn register_conversions(&mut self) {
+        let mut pairs: Vec<(String, String)> = vec![];
+        for (name, conversion) in &self.config.conversions {
+            let inverse: String = Conversion::inverse_name(name);
+            if conversion.factor == 0. || !conversion.factor.is_finite() {
+                self.warn_conversion(name, "has no inverse");
+            } else if self.cmdmap.contains_key(name) || self.cmdmap.contains_key(&inverse) {
+                self.warn_conversion(name, "is named after a command");
+            } else {
+                pairs.push((name.clone(), inverse));
+            }
+        }
+
+        for (name, inverse) in pairs {
+            self.build_native(&name, Self::c_conversion);
+            self.build_native(&inverse, Self::c_conversion);
+        }
+    }
+
+    fn warn_conversion(&self, name: &str, reason: &str) {
+        if self.config.show_warnings {
+            eprintln!(
+                "  {}: conversion [{}] (ignored) {reason}",
+                self.theme.yellow_canary_bold("warning"),
+                self.theme.blue_coffee_bold(name),
+            );
+        }
+    }
+
+    // conversion of the configuration named op ( or its inverse )
+    fn c_conversion(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let (conversion, inverse): (&Conversion, bool) = match self.config.conversions.get(op) {
+            Some(conversion) => (conversion, false),
+            None => self
+                .config
+                .conversions
+                .iter()
+                .find(|(name, _)| Conversion::inverse_name(name) == op)
+                .map(|(_, conversion)| (conversion, true))
+                .ok_or_else(|| CompError::InvalidArgument {
+                    op: op.to_string(),
+                    reason: String::from("conversion is no longer configured"),
+                })?,
+        };
+        let (factor, offset) = (conversion.factor, conversion.offset);
+
+        let a: f64 = self.pop_stack_f64()?;
+        let result: f64 = match inverse {
+            true => (a - offset) / factor,
+            false => a * factor + offset,
+        };
+        self.stack.push(Value::Float(result));
+
+        Ok(())
+    }
+
+    fn c_ascii(&mut self, _op: &str) -> Result<(), CompError> {
+        let out: String = (0..=255)
+            .map(|a| (a, a as u8 as char))
+            .filter(|(_val, c)| !c.is_control())
+            .map(|(val, c)| {
+                format!(
+                    "('{}' {})",
+                    self.theme.blue_coffee_bold(&c.to_string()),
+                    self.theme.grey_mouse(&val.to_string()),
+                )
+            }) // "('é'  233")
+            .fold(String::new(), |acc, s| acc + &s + "   ");
+
+        println!("{}", out);
+
+        Ok(())
+    }
+
+    /* ---- units ----------------------------------------------------------- */
+
+    // is any of the top n stack elements a quantity?
+    fn has_quantity(&self, n: usize) -> bool {
+        self.stack
+            .iter()
+            .rev()
+            .take(n)
+            .any(|a| matches!(a.inner(), Value::Quantity(..)))
+    }
+
+    // magnitude and unit ( none for numbers without a unit )
+    fn pop_stack_quantity(&mut self) -> (Value, Option<Unit>) {
+        match self.pop_stack_value() {
+            Value::Quantity(magnitude, unit) => (*magnitude, Some(unit)),
+            value => (value, None),
+        }
+    }
+
+    // replace magnitude on top of the stack with a quantity ( a dimensionless
+    // quantity becomes a number without a unit )
+    fn push_unit(&mut self, unit: Unit) -> Result<(), CompError> {
+        let magnitude: Value = self.pop_stack_value();
+        let value: Value = match unit.is_dimensionless() {
+            true => unit.scale(&magnitude).ok_or_else(|| CompError::Parse {
+                value: magnitude.to_string(),
+                kind: "f",
+            })?,
+            false => Value::Quantity(Box::new(magnitude), unit),
+        };
+        self.stack.push(value);
+
+        Ok(())
+    }
+
+    fn incompatible_units(op: &str, a: Option<&Unit>, b: Option<&Unit>) -> CompError {
+        let name = |unit: Option<&Unit>| unit.map_or(String::from("none"), Unit::to_string);
+
+        CompError::InvalidArgument {
+            op: op.to_string(),
+            reason: format!("incompatible units [{}] and [{}]", name(a), name(b)),
+        }
+    }
+
+    // magnitude converted between units ( error if the units do not measure
+    // the same kind of quantity )
+    fn convert_unit(
+        op: &str,
+        magnitude: &Value,
+        from: &Unit,
+        to: &Unit,
+        absolute: bool,
+    ) -> Result<Value, CompError> {
+        if !from.compatible(to) {
+            return Err(Self::incompatible_units(op, Some(from), Some(to)));
+        }
+
+        from.convert(magnitude, to, absolute)
+            .ok_or_else(|| CompError::Parse {
+                value: magnitude.to_string(),
+                kind: "f",
+            })
+    }
+
+    // ordering of quantities ( none if the units are not compatible )
+    fn quantity_cmp(a: &Value, a_unit: &Unit, b: &Value, b_unit: &Unit) -> Option<Ordering> {
+        match a_unit.compatible(b_unit) {
+            true => a.num_cmp(&b_unit.convert(b, a_unit, false)?),
+            false => None,
+        }
+    }
+
+    // add or subtract quantities with f ( b is converted to the unit of a )
+    fn cmdgen_sum_units(&mut self, op: &str, f: Command) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let (b, b_unit) = self.pop_stack_quantity();
+        let (a, a_unit) = self.pop_stack_quantity();
+        let (Some(a_unit), Some(b_unit)) = (&a_unit, &b_unit) else {
+            return Err(Self::incompatible_units(
+                op,
+                a_unit.as_ref(),
+                b_unit.as_ref(),
+            ));
+        };
+
+        if !a_unit.compatible(b_unit) {
+            return Err(Self::incompatible_units(op, Some(a_unit), Some(b_unit)));
+        }
+        let b: Value = Self::convert_unit(op, &b, b_unit, a_unit, false)?;
+        self.stack.push(a);
+        self.stack.push(b);
+        f(self, op)?;
+        self.push_unit(a_unit.clone())
+    }
+
+    // multiply or divide quantities with f ( the units are multiplied, or
+    // divided when sign is -1 )
+    fn cmdgen_product_units(&mut self, op: &str, f: Command, sign: i32) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let (b, b_unit) = self.pop_stack_quantity();
+        let (a, a_unit) = self.pop_stack_quantity();
+        let unit: Unit = match (a_unit, b_unit) {
+            (Some(a_unit), Some(b_unit)) => a_unit.mul(&b_unit.pow(sign)),
+            (Some(a_unit), None) => a_unit,
+            (None, Some(b_unit)) => b_unit.pow(sign),
+            (None, None) => unreachable!(),
+        };
+
+        self.stack.push(a);
+        self.stack.push(b);
+        f(self, op)?;
+        self.push_unit(unit)
+    }
+
+    // raise quantity to an integer power
+    fn cmdgen_power_units(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let (n, n_unit) = self.pop_stack_quantity();
+        let (a, a_unit) = self.pop_stack_quantity();
+        let (Some(a_unit), None) = (a_unit, &n_unit) else {
+            return Err(Self::incompatible_units(op, None, n_unit.as_ref()));
+        };
+        let power: i32 = n.to_int("i")?;
+
+        self.stack.push(a);
+        self.stack.push(n);
+        self.c_exp(op)?;
+        self.push_unit(a_unit.pow(power))
+    }
+
+    // single argument operation on a quantity ( the unit of the result is fu
+    // of the unit )
+    fn cmdgen_unary_units(
+        &mut self,
+        op: &str,
+        f: Command,
+        fu: fn(&Unit) -> Option<Unit>,
+    ) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let (a, a_unit) = self.pop_stack_quantity();
+        let a_unit: Unit = a_unit.unwrap();
+        let unit: Unit = fu(&a_unit).ok_or_else(|| CompError::InvalidArgument {
+            op: op.to_string(),
+            reason: format!("operation is not defined for unit [{a_unit}]"),
+        })?;
+
+        self.stack.push(a);
+        f(self, op)?;
+        self.push_unit(unit)
+    }
+
+    // convert number without a unit between units
+    fn cmdgen_convert(&mut self, op: &str, from: &str, to: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: Value = self.pop_stack_value();
+        let (from, to) = (Unit::parse(from).unwrap(), Unit::parse(to).unwrap());
+        self.stack
+            .push(Self::convert_unit(op, &a, &from, &to, true)?);
+
+        Ok(())
+    }
+
+    // quantity unit in -> quantity converted to unit
+    fn c_in(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let target: String = self.pop_stack_string()?;
+        let to: Unit = Unit::parse(&target).ok_or_else(|| CompError::BadArgument {
+            op: op.to_string(),
+            arg: target,
+        })?;
+
+        let (a, a_unit) = self.pop_stack_quantity();
+        let Some(a_unit) = a_unit else {
+            return Err(Self::incompatible_units(op, None, Some(&to)));
+        };
+        self.stack
+            .push(Self::convert_unit(op, &a, &a_unit, &to, true)?);
+        self.push_unit(to)
+    }
+
+    /* ---- dates and times ------------------------------------------------- */
+
+    // is any of the top n stack elements a date?
+    fn has_date(&self, n: usize) -> bool {
+        self.stack
+            .iter()
+            .rev()
+            .take(n)
+            .any(|a| matches!(a.inner(), Value::Date(_)))
+    }
+
+    // duration quantity as a time delta
+    fn time_delta(op: &str, duration: &Value) -> Result<TimeDelta, CompError> {
+        let invalid = |reason: String| CompError::InvalidArgument {
+            op: op.to_string(),
+            reason,
+        };
+        let seconds: Option<f64> = match duration {
+            Value::Quantity(magnitude, unit) if unit.is_time() => unit
+                .convert(magnitude, &Unit::parse("s").unwrap(), false)
+                .and_then(|s| s.as_f64()),
+            _ => return Err(invalid(format!("[{duration}] is not a duration"))),
+        };
+
+        seconds
+            .and_then(|s| TimeDelta::try_seconds(s.trunc() as i64).zip(Some(s.fract())))
+            .map(|(delta, fract)| delta + TimeDelta::nanoseconds((fract * 1e9).round() as i64))
+            .ok_or_else(|| invalid(format!("duration [{duration}] is out of range")))
+    }
+
+    // time delta as a duration quantity in seconds
+    fn duration_value(delta: TimeDelta) -> Value {
+        let seconds: Value = match delta.subsec_nanos() {
+            0 => Value::from(delta.num_seconds()),
+            nanos => Value::Float(delta.num_seconds() as f64 + nanos as f64 / 1e9),
+        };
+
+        Value::Quantity(Box::new(seconds), Unit::parse("s").unwrap())
+    }
+
+    // date plus or minus a duration, or the duration between dates ( sign is
+    // -1 for subtraction )
+    fn cmdgen_date(&mut self, op: &str, sign: i32) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let b: Value = self.pop_stack_value();
+        let a: Value = self.pop_stack_value();
+        let date: Option<DateTime<FixedOffset>> = match (&a, &b, sign) {
+            (Value::Date(x), Value::Date(y), -1) => {
+                self.stack
+                    .push(Self::duration_value(x.signed_duration_since(*y)));
+                return Ok(());
+            }
+            (Value::Date(x), duration, _) => {
+                x.checked_add_signed(Self::time_delta(op, duration)? * sign)
+            }
+            (duration, Value::Date(y), 1) => y.checked_add_signed(Self::time_delta(op, duration)?),
+            _ => {
+                return Err(CompError::InvalidArgument {
+                    op: op.to_string(),
+                    reason: format!("a date cannot be subtracted from [{a}]"),
+                })
+            }
+        };
+
+        let date = date.ok_or_else(|| CompError::InvalidArgument {
+            op: op.to_string(),
+            reason: String::from("date is out of range"),
+        })?;
+        self.stack.push(Value::Date(date));
+
+        Ok(())
+    }
+
+    fn c_now(&mut self, _op: &str) -> Result<(), CompError> {
+        let now: DateTime<FixedOffset> = Local::now().fixed_offset();
+        self.stack
+            .push(Value::Date(now.with_nanosecond(0).unwrap_or(now)));
+
+        Ok(())
+    }
+
+    fn c_today(&mut self, op: &str) -> Result<(), CompError> {
+        let midnight = Local::now().date_naive().and_hms_opt(0, 0, 0).unwrap();
+        let today = value::local_date(&midnight).ok_or_else(|| CompError::InvalidArgument {
+            op: op.to_string(),
+            reason: String::from("local midnight does not exist today"),
+        })?;
+        self.stack.push(Value::Date(today));
+
+        Ok(())
+    }
+
+    // date -> seconds since 1970-01-01T00:00:00Z
+    fn c_dateunix(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let date: DateTime<FixedOffset> = self.pop_stack_date()?;
+        self.stack.push(match date.nanosecond() {
+            0 => Value::from(date.timestamp()),
+            nanos => Value::Float(date.timestamp() as f64 + nanos as f64 / 1e9),
+        });
+
+        Ok(())
+    }
+
+    // seconds since 1970-01-01T00:00:00Z -> date ( in local time )
+    fn c_unixdate(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let seconds: f64 = self.pop_stack_f64()?;
+        let nanos: u32 = ((seconds - seconds.floor()) * 1e9)
+            .round()
+            .min(999_999_999.) as u32;
+        let date =
+            DateTime::<Utc>::from_timestamp(seconds.floor() as i64, nanos).ok_or_else(|| {
+                CompError::BadArgument {
+                    op: op.to_string(),
+                    arg: seconds.to_string(),
+                }
+            })?;
+        self.stack
+            .push(Value::Date(date.with_timezone(&Local).fixed_offset()));
+
+        Ok(())
+    }
+
+    fn c_weekday(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let date: DateTime<FixedOffset> = self.pop_stack_date()?;
+        self.stack
+            .push(Value::from(date.weekday().number_from_monday() as i64));
+
+        Ok(())
+    }
+
+    fn c_week(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let date: DateTime<FixedOffset> = self.pop_stack_date()?;
+        self.stack.push(Value::from(date.iso_week().week() as i64));
+
+        Ok(())
+    }
+
+    // sexagesimal form of x ( whole units, minutes, and seconds rounded to
+    // milliseconds, each followed by its mark )
+    fn sexagesimal(x: f64, marks: [&str; 3]) -> String {
+        let millis: u64 = (x.abs() * 3_600_000.).round() as u64;
+        let sign: &str = if x < 0. && millis > 0 { "-" } else { "" };
+        let mut seconds: String = format!("{:02}", millis / 1000 % 60);
+        if !millis.is_multiple_of(1000) {
+            seconds += format!(".{:03}", millis % 1000).trim_end_matches('0');
+        }
+
+        format!(
+            "{sign}{}{}{:02}{}{seconds}{}",
+            millis / 3_600_000,
+            marks[0],
+            millis / 60_000 % 60,
+            marks[1],
+            marks[2]
+        )
+    }
+
+    // decimal value of sexagesimal form ( units, minutes, and seconds
+    // separated by colons or degree, minute, and second marks )
+    fn from_sexagesimal(s: &str) -> Option<f64> {
+        let (sign, body): (f64, &str) = match s.strip_prefix('-') {
+            Some(body) => (-1., body),
+            None => (1., s),
+        };
+        let parts: Vec<f64> = body
+            .split([':', '°', '\'', '"'])
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse::<f64>().ok())
+            .collect::<Option<Vec<f64>>>()?;
+        if parts.is_empty() || parts.len() > 3 {
+            return None;
+        }
+
+        Some(
+            sign * parts
+                .iter()
+                .zip([1., 60., 3600.])
+                .map(|(x, d)| x / d)
+                .sum::<f64>(),
+        )
+    }
+
+    // hours ( or duration ) -> hours:minutes:seconds
+    fn c_hms(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let (a, a_unit) = self.pop_stack_quantity();
+        let hours: f64 = match a_unit {
+            Some(unit) => {
+                Self::convert_unit(op, &a, &unit, &Unit::parse("h").unwrap(), false)?.to_f64()?
+            }
+            None => a.to_f64()?,
+        };
+        self.stack
+            .push(Value::Str(Self::sexagesimal(hours, [":", ":", ""])));
+
+        Ok(())
+    }
+
+    fn c_hmsdec(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_sexagesimal(op)
+    }
+
+    // degrees -> degrees°minutes'seconds"
+    fn c_dms(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let degrees: f64 = self.pop_stack_f64()?;
+        self.stack
+            .push(Value::Str(Self::sexagesimal(degrees, ["°", "'", "\""])));
+
+        Ok(())
+    }
+
+    fn c_dmsdec(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_sexagesimal(op)
+    }
+
+    // sexagesimal form -> decimal value
+    fn cmdgen_sexagesimal(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let a: String = self.pop_stack_string()?;
+        let x: f64 = Self::from_sexagesimal(&a).ok_or_else(|| CompError::BadArgument {
+            op: op.to_string(),
+            arg: a.clone(),
+        })?;
+        self.stack.push(Value::Float(x));
+
+        Ok(())
+    }
+
+    /* ---- binary operations ----------------------------------------------- */
+
+    // word size and signedness ( unsigned 64-bit words when programmer mode is
+    // off )
+    fn word(&self) -> (u32, bool) {
+        match self.config.word_size {
+            0 => (64, false),
+            bits => (bits, self.config.signed),
+        }
+    }
+
+    fn word_mask(bits: u32) -> u128 {
+        u128::MAX >> (128 - bits)
+    }
+
+    // word bits extended to 128 bits with the sign bit of the word
+    fn sign_extend(a: u128, bits: u32) -> i128 {
+        ((a << (128 - bits)) as i128) >> (128 - bits)
+    }
+
+    // integer wrapped to the word size ( negative integers wrap to their two's
+    // complement )
+    fn pop_stack_word(&mut self) -> Result<u128, CompError> {
+        let (bits, _) = self.word();
+        let a: BigInt = self.pop_stack_value().to_int("i")?;
+
+        Ok((a & BigInt::from(Self::word_mask(bits))).to_u128().unwrap())
+    }
+
+    // word as an integer ( negative if the word is signed and its sign bit is
+    // set )
+    fn word_value(&self, a: u128) -> Value {
+        let (bits, signed) = self.word();
+        match signed {
+            true => Value::from(BigInt::from(Self::sign_extend(a, bits))),
+            false => Value::from(BigInt::from(a)),
+        }
+    }
+
+    // word operation ( f is called with the words and the word size and its
+    // result is truncated to the word size )
+    fn cmdgen_word(
+        &mut self,
+        args: usize,
+        op: &str,
+        f: fn(u128, u128, u32) -> u128,
+    ) -> Result<(), CompError> {
+        self.check_stack_error(args, op)?;
+
+        let (bits, _) = self.word();
+        let b: u128 = match args {
+            2 => self.pop_stack_word()?,
+            _ => 0,
+        };
+        let a: u128 = self.pop_stack_word()?;
+        self.stack
+            .push(self.word_value(f(a, b, bits) & Self::word_mask(bits)));
+
+        Ok(())
+    }
+
+    // shift or rotate word by n bits ( word n op -> word )
+    fn cmdgen_shift(&mut self, op: &str, f: fn(u128, u32, u32) -> u128) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let (bits, _) = self.word();
+        let n: u32 = self.pop_stack_value().to_int("u")?;
+        let a: u128 = self.pop_stack_word()?;
+        self.stack
+            .push(self.word_value(f(a, n, bits) & Self::word_mask(bits)));
+
+        Ok(())
+    }
+
+    // bit n of word ( error if the word has no bit n )
+    fn pop_stack_bit(&mut self, op: &str) -> Result<u128, CompError> {
+        let (bits, _) = self.word();
+        let n: u32 = self.pop_stack_value().to_int("u")?;
+        if n >= bits {
+            return Err(CompError::BadArgument {
+                op: op.to_string(),
+                arg: n.to_string(),
+            });
+        }
+
+        Ok(1 << n)
+    }
+
+    fn c_not(&mut self, op: &str) -> Result<(), CompError> {
+        match self.config.word_size {
+            0 => self.cmdgen_sint(1, op, |a, _| !a),
+            _ => self.cmdgen_word(1, op, |a, _, _| !a),
+        }
+    }
+
+    fn c_and(&mut self, op: &str) -> Result<(), CompError> {
+        match self.config.word_size {
+            0 => self.cmdgen_sint(2, op, |a, b| a & b),
+            _ => self.cmdgen_word(2, op, |a, b, _| a & b),
+        }
+    }
+
+    fn c_nand(&mut self, op: &str) -> Result<(), CompError> {
+        match self.config.word_size {
+            0 => self.cmdgen_sint(2, op, |a, b| !(a & b)),
+            _ => self.cmdgen_word(2, op, |a, b, _| !(a & b)),
+        }
+    }
+
+    fn c_or(&mut self, op: &str) -> Result<(), CompError> {
+        match self.config.word_size {
+            0 => self.cmdgen_sint(2, op, |a, b| a | b),
+            _ => self.cmdgen_word(2, op, |a, b, _| a | b),
+        }
+    }
+
+    fn c_nor(&mut self, op: &str) -> Result<(), CompError> {
+        match self.config.word_size {
+            0 => self.cmdgen_sint(2, op, |a, b| !(a | b)),
+            _ => self.cmdgen_word(2, op, |a, b, _| !(a | b)),
+        }
+    }
+
+    fn c_xor(&mut self, op: &str) -> Result<(), CompError> {
+        match self.config.word_size {
+            0 => self.cmdgen_sint(2, op, |a, b| a ^ b),
+            _ => self.cmdgen_word(2, op, |a, b, _| a ^ b),
+        }
+    }
+
+    fn c_ones(&mut self, op: &str) -> Result<(), CompError> {
+        if self.config.word_size == 0 {
+            return self.cmdgen_uint(1, op, |a, _| BigUint::from(a.count_ones()));
+        }
+        self.check_stack_error(1, op)?;
+
+        let a: u128 = self.pop_stack_word()?;
+        self.stack.push(Value::from(a.count_ones() as i64));
+
+        Ok(())
+    }
+
+    // bits ws -> ( set word size to 8, 16, 32, 64, or 128 bits, or turn
+    // programmer mode off with 0 )
+    fn c_word_size(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let bits: u32 = self.pop_stack_value().to_int("u")?;
+        if ![0, 8, 16, 32, 64, 128].contains(&bits) {
+            return Err(CompError::BadArgument {
+                op: op.to_string(),
+                arg: bits.to_string(),
+            });
+        }
+        self.config.word_size = bits;
+
+        Ok(())
+    }
+
+    fn c_signed(&mut self, _op: &str) -> Result<(), CompError> {
+        self.config.signed = true;
+
+        Ok(())
+    }
+
+    fn c_unsigned(&mut self, _op: &str) -> Result<(), CompError> {
+        self.config.signed = false;
+
+        Ok(())
+    }
+
+    fn c_shl(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_shift(op, |a, n, _| a.checked_shl(n).unwrap_or(0))
+    }
+
+    fn c_shr(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_shift(op, |a, n, _| a.checked_shr(n).unwrap_or(0))
+    }
+
+    fn c_sar(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_shift(op, |a, n, bits| {
+            (Self::sign_extend(a, bits) >> n.min(127)) as u128
+        })
+    }
+
+    fn c_rol(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_shift(op, |a, n, bits| match n % bits {
+            0 => a,
+            n => a << n | a >> (bits - n),
+        })
+    }
+
+    fn c_ror(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_shift(op, |a, n, bits| match n % bits {
+            0 => a,
+            n => a >> n | a << (bits - n),
+        })
+    }
+
+    fn c_bset(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let bit: u128 = self.pop_stack_bit(op)?;
+        let a: u128 = self.pop_stack_word()?;
+        self.stack.push(self.word_value(a | bit));
+
+        Ok(())
+    }
+
+    fn c_bclr(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let bit: u128 = self.pop_stack_bit(op)?;
+        let a: u128 = self.pop_stack_word()?;
+        self.stack.push(self.word_value(a & !bit));
+
+        Ok(())
+    }
+
+    fn c_btest(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let bit: u128 = self.pop_stack_bit(op)?;
+        let a: u128 = self.pop_stack_word()?;
+        self.stack.push(Value::Bool(a & bit != 0));
+
+        Ok(())
+    }
+
+    fn c_clz(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let (bits, _) = self.word();
+        let a: u128 = self.pop_stack_word()?;
+        self.stack
+            .push(Value::from((a.leading_zeros() - (128 - bits)) as i64));
+
+        Ok(())
+    }
+
+    fn c_ctz(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let (bits, _) = self.word();
+        let a: u128 = self.pop_stack_word()?;
+        self.stack
+            .push(Value::from(a.trailing_zeros().min(bits) as i64));
+
+        Ok(())
+    }
+
+    fn c_bswap(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_word(1, op, |a, _, bits| a.swap_bytes() >> (128 - bits))
+    }
+
+    // word -> bit pattern of the word ( negative integers are shown in two's
+    // complement )
+    fn c_twos(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let (bits, _) = self.word();
+        let a: u128 = self.pop_stack_word()?;
+        self.stack
+            .push(Value::Str(format!("{a:0width$b}", width = bits as usize)));
+
+        Ok(())
+    }
+
+    /* ---- control flow ---------------------------------------------------- */
+
+    fn c_equal(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let b: Value = self.pop_stack_value();
+        let a: Value = self.pop_stack_value();
+
+        self.stack.push(Value::Bool(Self::equal(&a, &b)));
+
+        Ok(())
+    }
+
+    fn c_lessthan(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_cmp(op, Ordering::is_lt)
+    }
+
+    fn c_lessthanorequal(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_cmp(op, Ordering::is_le)
+    }
+
+    fn c_greaterthan(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_cmp(op, Ordering::is_gt)
+    }
+
+    fn c_greaterthanorequal(&mut self, op: &str) -> Result<(), CompError> {
+        self.cmdgen_cmp(op, Ordering::is_ge)
+    }
+
+    // parse the block opened by op from the operations list and evaluate it
+    fn c_block(&mut self, op: &str) -> Result<(), CompError> {
+        let node: Node = parser::parse_block(op, &mut self.ops)?;
+
+        match self.config.compile {
+            true => self.run_code(bytecode::compile(&[node], &self.cmdmap)),
+            false => self.evaluate_block(Block::from([node])),
+        }
+    }
+
+    // evaluate parsed operations. user function calls and conditional
+    // branches are evaluated in place ( a function called last in a block
+    // replaces it ) so that recursion depth is not limited by the native stack.
+    fn evaluate_block(&mut self, block: Block) -> Result<(), CompError> {
+        let mut frames: Vec<(Block, usize, bool)> = vec![(block, 0, false)]; // nodes, offset, and function call
+
+        while self.flow.is_none() {
+            let Some((nodes, ip, _)) = frames.last_mut() else {
+                break;
+            };
+            let nodes: Block = nodes.clone();
+            let Some(node) = nodes.get(*ip) else {
+                let len: usize = frames.len() - 1;
+                self.drop_frames(&mut frames, len); // end of block
+                continue;
+            };
+            *ip += 1;
+            let last: bool = *ip == nodes.len();
+
+            match node {
+                Node::Op(op) if !self.cmdmap.contains_key(op) => match self.is_user_function(op) {
+                    Some(index) => {
+                        // a function called last replaces the block ( and its call )
+                        let tail: bool = last && frames.pop().is_some_and(|(_, _, call)| call);
+                        self.enter(op, tail);
+                        frames.push((self.fns[index].body.clone(), 0, true));
+                    }
+                    None => self.evaluate_op(op)?,
+                },
+                Node::If {
+                    op,
+                    then,
+                    otherwise,
+                } => {
+                    let branch: &Block = if self.condition(op)? { then } else { otherwise };
+                    // a conditional evaluated last replaces the block ( and takes
+                    // over its call )
+                    let call: bool = last && frames.pop().is_some_and(|(_, _, call)| call);
+                    frames.push((branch.clone(), 0, call));
+                }
+                _ => self.evaluate_node(node)?,
+            }
+        }
+        self.drop_frames(&mut frames, 0); // break or continue
+
+        Ok(())
+    }
+
+    fn evaluate_node(&mut self, node: &Node) -> Result<(), CompError> {
+        match node {
+            Node::Op(op) => self.evaluate_op(op)?,
+            Node::Quoted(s) => self.stack.push(Value::Str(s.clone())),
+            Node::Function { name, fops, body } => {
+                self.define_function(name, fops, body);
+            }
+            Node::Lambda { fops, body } => self.stack.push(Value::Quote(Arc::new(Quote::new(
+                fops.clone(),
+                body.clone(),
+            )))),
+            Node::Push(value) => self.stack.push(value.clone()),
+            Node::Comment => (),
+            Node::If { .. } => self.evaluate_block(Block::from([node.clone()]))?,
+            // begin .. condition until
+            Node::Until(body) => {
+                let mut index: i64 = 0;
+                while self.run_loop_body(body, index)? {
+                    self.check_stack_error(1, "until")?;
+                    if self.pop_stack_value().to_bool()? {
+                        break;
+                    }
+                    index += 1;
+                }
+            }
+            // begin .. condition while .. repeat
+            Node::While(body, repeat_body) => {
+                let mut index: i64 = 0;
+                while self.run_loop_body(body, index)? {
+                    self.check_stack_error(1, "while")?;
+                    if !self.pop_stack_value().to_bool()?
+                        || !self.run_loop_body(repeat_body, index)?
+                    {
+                        break;
+                    }
+                    index += 1;
+                }
+            }
+            // begin .. repeat ( exited with break )
+            Node::Repeat(body) => {
+                let mut index: i64 = 0;
+                while self.run_loop_body(body, index)? {
+                    index += 1;
+                }
+            }
+            Node::Times(body) => {
+                self.check_stack_error(1, "times")?;
+
+                let count: i64 = self.pop_stack_i64()?;
+
+                for index in 0..count {
+                    if !self.run_loop_body(body, index)? {
+                        break;
+                    }
+                }
+            }
+            Node::Do(body) => {
+                self.check_stack_error(2, "do")?;
+
+                let start: i64 = self.pop_stack_i64()?;
+                let limit: i64 = self.pop_stack_i64()?;
+
+                for index in start..limit {
+                    if !self.run_loop_body(body, index)? {
+                        break;
+                    }
+                }
+            }
+            Node::Forget(name) => self.forget("forget", name),
+            Node::Line(loc) => self.location = Some(loc.clone()),
+            Node::Bind(names) => self.bind(names)?,
+            Node::Local(name) => self.local(name)?,
+        }
+
+        Ok(())
+    }
+
+    // add user-defined function ( replacing any existing definition )
+    fn define_function(&mut self, name: &str, fops: &[String], body: &Block) {
+        if let Some(index) = self.is_user_function(name) {
+            self.fns.remove(index);
+        }
+
+        self.fns.push(Function {
+            name: name.to_string(),
+            fops: fops.to_vec(),
+            body: body.clone(),
+        });
+        self.code
+            .insert(name.to_string(), bytecode::compile(body, &self.cmdmap));
+    }
+
+    // condition of if ( truth value ) or ifeq ( equality of top two values )
+    fn condition(&mut self, op: &str) -> Result<bool, CompError> {
+        match op {
+            "ifeq" => {
+                self.check_stack_error(2, op)?;
+
+                let b = self.pop_stack_f64()?;
+                let a = self.pop_stack_f64()?;
+
+                Ok(a == b)
+            }
+            _ => {
+                self.check_stack_error(1, op)?;
+
+                self.pop_stack_value().to_bool()
+            }
+        }
+    }
+
+    fn c_index(&mut self, op: &str) -> Result<(), CompError> {
+        match self.loops.last() {
+            Some(index) => {
+                self.stack.push(Value::from(*index));
+                Ok(())
+            }
+            None => Err(CompError::OutsideLoop { op: op.to_string() }),
+        }
+    }
+
+    fn c_break(&mut self, op: &str) -> Result<(), CompError> {
+        if self.loops.is_empty() {
+            return Err(CompError::OutsideLoop { op: op.to_string() });
+        }
+        self.flow = Some(Flow::Break);
+
+        Ok(())
+    }
+
+    fn c_continue(&mut self, op: &str) -> Result<(), CompError> {
+        if self.loops.is_empty() {
+            return Err(CompError::OutsideLoop { op: op.to_string() });
+        }
+        self.flow = Some(Flow::Continue);
+
+        Ok(())
+    }
+
+    // evaluate one iteration of a loop body ( false is returned if the loop
+    // was exited with break )
+    fn run_loop_body(&mut self, body: &Block, index: i64) -> Result<bool, CompError> {
+        self.loops.push(index);
+
+        let result = self.evaluate_block(body.clone());
+
+        self.loops.pop();
+        result?;
+
+        match self.flow.take() {
+            Some(Flow::Break) => Ok(false),
+            Some(Flow::Continue) | None => Ok(true),
+        }
+    }
+
+    /* ---- bytecode -------------------------------------------------------- */
+
+    // run compiled operations ( loops started by the code are ended on error )
+    fn run_code(&mut self, code: Code) -> Result<(), CompError> {
+        let depth: usize = self.loops.len();
+
+        let result = self.execute(code);
+
+        self.loops.truncate(depth);
+        result
+    }
+
+    fn execute(&mut self, code: Code) -> Result<(), CompError> {
+        let mut frames: Vec<(Code, usize, bool)> = vec![(code, 0, false)]; // code, offset, and function call
+        let mut loops: Vec<LoopFrame> = vec![];
+
+        while let Some((code, ip, _)) = frames.last_mut() {
+            let code: Code = code.clone();
+            let Some(instr) = code.get(*ip) else {
+                let len: usize = frames.len() - 1;
+                self.drop_frames(&mut frames, len); // end of function
+                continue;
+            };
+            *ip += 1;
+            let next: usize = *ip;
+
+            match instr {
+                Instr::Native(f, op) => f(self, op)?,
+                Instr::Push(value) => self.stack.push(value.clone()),
+                // native command registered after compilation
+                Instr::Word(op, _) if self.cmdmap.contains_key(op) => {
+                    let f = self.cmdmap[op];
+                    f(self, op)?
+                }
+                Instr::Word(op, value) => match self.code.get(op) {
+                    Some(body) => {
+                        let body: Code = body.clone();
+                        // function called last replaces caller ( and its call )
+                        let tail: bool =
+                            next == code.len() && frames.pop().is_some_and(|(_, _, call)| call);
+                        self.enter(op, tail);
+                        frames.push((body, 0, true));
+                    }
+                    None => {
+                        let value: Value = self.mem.get(op).unwrap_or(value).clone();
+                        self.stack.push(value);
+                    }
+                },
+                Instr::Define { name, fops, body } => self.define_function(name, fops, body),
+                Instr::Forget(name) => self.forget("forget", name),
+                Instr::Line(loc) => self.location = Some(loc.clone()),
+                Instr::Bind(names) => self.bind(names)?,
+                Instr::Local(name) => self.local(name)?,
+                Instr::Test(op, target) => {
+                    if !self.condition(op)? {
+                        Self::jump(&mut frames, *target);
+                    }
+                }
+                Instr::Jump(target) => Self::jump(&mut frames, *target),
+                Instr::Times(exit) | Instr::Do(exit) => {
+                    let (start, limit): (i64, i64) = match instr {
+                        Instr::Times(_) => {
+                            self.check_stack_error(1, "times")?;
+                            (0, self.pop_stack_i64()?)
+                        }
+                        _ => {
+                            self.check_stack_error(2, "do")?;
+                            let start: i64 = self.pop_stack_i64()?;
+                            (start, self.pop_stack_i64()?)
+                        }
+                    };
+                    match start < limit {
+                        true => {
+                            loops.push(LoopFrame {
+                                frame: frames.len() - 1,
+                                start: next,
+                                exit: *exit,
+                                check: exit - 1,
+                                cont: exit - 1,
+                                limit,
+                            });
+                            self.loops.push(start);
+                        }
+                        false => Self::jump(&mut frames, *exit),
+                    }
+                }
+                Instr::Begin(exit, check) => {
+                    loops.push(LoopFrame {
+                        frame: frames.len() - 1,
+                        start: next,
+                        exit: *exit,
+                        check: *check,
+                        cont: *check,
+                        limit: i64::MAX,
+                    });
+                    self.loops.push(0);
+                }
+                Instr::Loop | Instr::Repeat => {
+                    let state: &mut LoopFrame = loops.last_mut().unwrap();
+                    let index: &mut i64 = self.loops.last_mut().unwrap();
+                    *index += 1;
+                    match *index < state.limit {
+                        true => {
+                            state.cont = state.check;
+                            Self::jump(&mut frames, state.start);
+                        }
+                        false => {
+                            loops.pop();
+                            self.loops.pop();
+                        }
+                    }
+                }
+                Instr::Until | Instr::While(_) => {
+                    let op: &str = if let Instr::Until = instr {
+                        "until"
+                    } else {
+                        "while"
+                    };
+                    self.check_stack_error(1, op)?;
+
+                    let condition: bool = self.pop_stack_value().to_bool()?;
+                    let state: &mut LoopFrame = loops.last_mut().unwrap();
+                    match (instr, condition) {
+                        (Instr::Until, false) => {
+                            *self.loops.last_mut().unwrap() += 1;
+                            Self::jump(&mut frames, state.start);
+                        }
+                        (Instr::While(repeat), true) => state.cont = *repeat,
+                        _ => {
+                            Self::jump(&mut frames, state.exit);
+                            loops.pop();
+                            self.loops.pop();
+                        }
+                    }
+                }
+            }
+
+            // break or continue
+            if let Some(flow) = self.flow.take() {
+                let Some(state) = loops.last() else {
+                    self.flow = Some(flow); // loop is outside of this code
+                    self.drop_frames(&mut frames, 0);
+                    return Ok(());
+                };
+                self.drop_frames(&mut frames, state.frame + 1);
+                let target: usize = match flow {
+                    Flow::Break => state.exit,
+                    Flow::Continue => state.cont,
+                };
+                if let Flow::Break = flow {
+                    loops.pop();
+                    self.loops.pop();
+                }
+                Self::jump(&mut frames, target);
+            }
+        }
+
+        Ok(())
+    }
+
+    // continue current function at target offset
+    fn jump(frames: &mut [(Code, usize, bool)], target: usize) {
+        if let Some((_, ip, _)) = frames.last_mut() {
+            *ip = target;
+        }
+    }
+
+    /* ---- call stack ------------------------------------------------------ */
+
+    // record call of user-defined function from the current location ( a tail
+    // call replaces the call of the calling function )
+    fn enter(&mut self, name: &str, tail: bool) {
+        let call = Call {
+            name: name.to_string(),
+            caller: self.calls.last().map(|call| call.name.clone()),
+            from: self.location.clone(),
+            locals: HashMap::new(),
+        };
+        if tail {
+            self.calls.pop();
+        }
+        self.calls.push(call);
+    }
+
+    // end call of user-defined function ( the location returns to the call )
+    fn leave(&mut self) {
+        if let Some(call) = self.calls.pop() {
+            self.location = call.from;
+        }
+    }
+
+    // leave call entered at depth ( and any calls it made on error, after the
+    // error is traced )
+    fn leave_call(&mut self, depth: usize, result: Result<(), CompError>) -> Result<(), CompError> {
+        let result = result.map_err(|error| self.traced(error));
+        self.calls.truncate(depth + 1);
+        self.leave();
+
+        result
+    }
+
+    // pop values into local variables of the current call ( the last name is
+    // bound to the top of the stack )
+    fn bind(&mut self, names: &[String]) -> Result<(), CompError> {
+        if self.calls.is_empty() {
+            return Err(CompError::InvalidArgument {
+                op: String::from("->"),
+                reason: String::from("local variables can only be bound in a function"),
+            });
+        }
+        self.check_stack_error(names.len(), "->")?;
+
+        let values: Vec<Value> = self.stack.split_off(self.stack.len() - names.len());
+        if let Some(call) = self.calls.last_mut() {
+            call.locals.extend(names.iter().cloned().zip(values));
+        }
+
+        Ok(())
+    }
+
+    // push value of local variable of the current call
+    fn local(&mut self, name: &str) -> Result<(), CompError> {
+        let value: Option<Value> = self
+            .calls
+            .last()
+            .and_then(|call| call.locals.get(name).cloned());
+        match value {
+            Some(value) => {
+                self.stack.push(value);
+                Ok(())
+            }
+            None => Err(CompError::InvalidArgument {
+                op: name.to_string(),
+                reason: String::from("local variable has not been bound"),
+            }),
+        }
+    }
+
+    // remove frames above len ( ending the function calls they were running )
+    fn drop_frames<T>(&mut self, frames: &mut Vec<(T, usize, bool)>, len: usize) {
+        while frames.len() > len {
+            if let Some((_, _, true)) = frames.pop() {
+                self.leave();
+            }
+        }
+    }
+
+    // error with the location of the failing operation and the user-defined
+    // function calls that led to it
+    fn traced(&self, error: CompError) -> CompError {
+        if let CompError::Trace(..) = error {
+            return error;
+        }
+
+        let mut trace: Vec<String> = vec![];
+        match (self.calls.last(), &self.location) {
+            (Some(call), Some(loc)) => trace.push(format!("in {} ({loc})", call.name)),
+            (Some(call), None) => trace.push(format!("in {}", call.name)),
+            (None, Some(loc)) => trace.push(format!("at {loc}")),
+            (None, None) => return error,
+        }
+        for call in self.calls.iter().rev() {
+            match (&call.caller, &call.from) {
+                (Some(caller), Some(loc)) => trace.push(format!("called from {caller} ({loc})")),
+                (Some(caller), None) => trace.push(format!("called from {caller}")),
+                (None, Some(loc)) => trace.push(format!("called from {loc}")),
+                (None, None) => (),
+            }
+        }
+
+        CompError::Trace(Box::new(error), trace)
+    }
+
+    /* ---- RGB colors ------------------------------------------------------ */
+
+    fn c_rgb(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(3, op)?;
+
+        let b = self.pop_stack_u8()?;
+        let g = self.pop_stack_u8()?;
+        let r = self.pop_stack_u8()?;
+
+        self.stack.push(Value::Str(self.output_rgb_dec(cor::Color {
+            r,
+            g,
+            b,
+            bold: false,
+        })));
+        self.stack
+            .push(Value::Str(self.output_rgb_hex_bg(cor::Color {
+                r,
+                g,
+                b,
+                bold: false,
+            })));
+
+        Ok(())
+    }
+
+    fn c_rgbh(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(3, op)?;
+
+        let b = self.pop_stack_u8_from_hex()?;
+        let g = self.pop_stack_u8_from_hex()?;
+        let r = self.pop_stack_u8_from_hex()?;
+
+        self.stack.push(Value::Str(self.output_rgb_dec(cor::Color {
+            r,
+            g,
+            b,
+            bold: false,
+        })));
+        self.stack
+            .push(Value::Str(self.output_rgb_hex_bg(cor::Color {
+                r,
+                g,
+                b,
+                bold: false,
+            })));
+
+        Ok(())
+    }
+
+    fn c_rgb_avg(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let b = self.pop_stack_string()?;
+        let a = self.pop_stack_string()?;
+
+        if a.len() != 6 || b.len() != 6 {
+            return Err(CompError::InvalidArgument {
+                op: op.to_string(),
+                reason: String::from("expected two six-digit hexadecimal colors"),
+            });
+        }
+
+        let parse_hex = |color: &str, sh: &str| {
+            u16::from_str_radix(sh, 16).map_err(|_| CompError::Parse {
+                value: color.to_string(),
+                kind: "i_h",
+            })
+        };
+
+        let avg_hex = |range: std::ops::Range<usize>| -> Result<u8, CompError> {
+            Ok(((parse_hex(&a, &a[range.clone()])? + parse_hex(&b, &b[range])?) / 2) as u8)
+        };
+
+        let r = avg_hex(0..2)?;
+        let g = avg_hex(2..4)?;
+        let b = avg_hex(4..6)?;
+
+        self.stack.push(Value::Str(self.output_rgb_dec(cor::Color {
+            r,
+            g,
+            b,
+            bold: false,
+        })));
+        self.stack
+            .push(Value::Str(self.output_rgb_hex_bg(cor::Color {
+                r,
+                g,
+                b,
+                bold: false,
+            })));
+
+        Ok(())
+    }
+
+    fn c_rgb_mult(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(4, op)?;
+
+        let factor = self.pop_stack_f64()?;
+        let b = self.pop_stack_u8()?;
+        let g = self.pop_stack_u8()?;
+        let r = self.pop_stack_u8()?;
+
+        let bound = |x: f64| -> u8 {
+            if x > 255.0 {
+                255
+            } else if x < 0.0 {
+                0
+            } else {
+                x as u8
+            }
+        };
+
+        let rnew = bound(r as f64 * factor);
+        let gnew = bound(g as f64 * factor);
+        let bnew = bound(b as f64 * factor);
+
+        self.stack.push(Value::Str(self.output_rgb_dec(cor::Color {
+            r: rnew,
+            g: gnew,
+            b: bnew,
+            bold: false,
+        })));
+        self.stack
+            .push(Value::Str(self.output_rgb_hex_bg(cor::Color {
+                r: rnew,
+                g: gnew,
+                b: bnew,
+                bold: false,
+            })));
+
+        Ok(())
+    }
+
+    fn c_rgbh_mult(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(4, op)?;
+
+        let factor = self.pop_stack_f64()?;
+        let b = self.pop_stack_u8_from_hex()?;
+        let g = self.pop_stack_u8_from_hex()?;
+        let r = self.pop_stack_u8_from_hex()?;
+
+        let bound = |x: f64| -> u8 {
+            if x > 255.0 {
+                255
+            } else if x < 0.0 {
+                0
+            } else {
+                x as u8
+            }
+        };
+
+        let rnew = bound(r as f64 * factor);
+        let gnew = bound(g as f64 * factor);
+        let bnew = bound(b as f64 * factor);
+
+        self.stack.push(Value::Str(self.output_rgb_dec(cor::Color {
+            r: rnew,
+            g: gnew,
+            b: bnew,
+            bold: false,
+        })));
+        self.stack
+            .push(Value::Str(self.output_rgb_hex_bg(cor::Color {
+                r: rnew,
+                g: gnew,
+                b: bnew,
+                bold: false,
+            })));
+
+        Ok(())
+    }
+
+    /* ---- modules --------------------------------------------------------- */
+
+    fn c_import(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let name: String = self.pop_stack_string()?;
+
+        let path: PathBuf = self
+            .find_module(&name)
+            .ok_or_else(|| CompError::UnknownFile {
+                path: name.to_string(),
+                reason: String::from("not found in module search path"),
+            })?;
+
+        if self.importing.contains(&path) {
+            return Err(CompError::InvalidArgument {
+                op: op.to_string(),
+                reason: format!("circular import of [{}]", path.display()),
+            });
+        }
+        if self.imported.contains(&path) {
+            return Ok(()); // modules are only loaded once
+        }
+
+        self.evaluate_module(path, &name)
+    }
+
+    // evaluate module file operations before the rest of the operations list
+    fn evaluate_module(&mut self, path: PathBuf, origin: &str) -> Result<(), CompError> {
+        let contents: String =
+            fs::read_to_string(&path).map_err(|error| CompError::UnknownFile {
+                path: path.display().to_string(),
+                reason: error.to_string(),
+            })?;
+
+        let module_ops: VecDeque<Token> = lexer::lex(&contents, origin)?.into();
+        let ops: VecDeque<Token> = std::mem::replace(&mut self.ops, module_ops);
+        let location: Option<Loc> = self.location.clone();
+        self.importing.push(path);
+
+        let result = self.evaluate_ops();
+
+        let path: PathBuf = self.importing.pop().unwrap();
+        self.ops = ops;
+        result.map_err(|error| match (error, &location) {
+            (CompError::Trace(error, mut trace), Some(loc)) => {
+                trace.push(format!("imported from {loc}"));
+                CompError::Trace(error, trace)
+            }
+            (error, _) => error,
+        })?;
+        self.location = location;
+
+        self.imported.push(path);
+
+        Ok(())
+    }
+
+    // locate module file ( relative paths are searched for in the directory of
+    // the importing file or script, the COMP_PATH directories, then the home
+    // folder )
+    fn find_module(&self, name: &str) -> Option<PathBuf> {
+        let name: &Path = Path::new(name);
+        if name.is_absolute() {
+            return fs::canonicalize(name).ok();
+        }
+
+        let mut search_path: Vec<PathBuf> = vec![];
+        match (self.importing.last(), &self.script_dir) {
+            (Some(module), _) => search_path.extend(module.parent().map(Path::to_path_buf)),
+            (None, Some(dir)) => search_path.push(dir.clone()),
+            (None, None) => search_path.push(PathBuf::from(".")),
+        }
+        if let Some(paths) = env::var_os(SEARCH_PATH_VAR) {
+            search_path.extend(env::split_paths(&paths));
+        }
+        search_path.extend(home::home_dir());
+
+        search_path
+            .iter()
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+            .and_then(|path| fs::canonicalize(path).ok())
+    }
+
+    /* ---- higher-order functions ------------------------------------------ */
+
+    fn c_map(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+
+        // list [ q ] map -> list
+        if self.has_list(0) {
+            let mut mapped: Vec<Value> = vec![];
+            for x in self.pop_stack_list()? {
+                mapped.extend(self.apply_quote(&quote, vec![x])?);
+            }
+            self.stack.push(Value::List(mapped));
+            return Ok(());
+        }
+
+        // execute anonymous function on each stack element (backwards)
+        for _ in 0..self.stack.len() {
+            self.evaluate_op("rot")?; // rotate stack
+            self.call_quote(&quote)?; // execute anonymous function
+        }
+
+        Ok(())
+    }
+
+    fn c_fold(&mut self, op: &str) -> Result<(), CompError> {
+        let list: bool = self.has_list(2);
+        self.check_stack_error(if list { 3 } else { 4 }, op)?;
+
+        // anonymous function is below the initial value
+        let init: Value = self.pop_stack_literal();
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+
+        // list [ q ] init fold -> value
+        if list {
+            let mut acc: Value = init;
+            for x in self.pop_stack_list()? {
+                acc = self.apply_quote_once(&quote, vec![acc, x])?;
+            }
+            self.stack.push(acc);
+            return Ok(());
+        }
+
+        self.stack.push(init);
+
+        // execute anonymous function on each stack element (backwards)
+        for _ in 0..(self.stack.len() - 1) {
+            self.evaluate_op("rot")?; // rotate stack
+            self.call_quote(&quote)?; // execute anonymous function
+        }
+
+        Ok(())
+    }
+
+    fn c_scan(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+
+        // list [ q ] scan -> list
+        if self.has_list(0) {
+            let mut scanned: Vec<Value> = vec![];
+            for x in self.pop_stack_list()? {
+                let acc: Value = match scanned.last() {
+                    Some(acc) => self.apply_quote_once(&quote, vec![acc.clone(), x])?,
+                    None => x,
+                };
+                scanned.push(acc);
+            }
+            self.stack.push(Value::List(scanned));
+            return Ok(());
+        }
+
+        // execute anonymous function on each stack element (backwards)
+        self.evaluate_op("rot")?; // rotate stack
+        for _ in 0..(self.stack.len() - 1) {
+            self.evaluate_op("dup")?; // copy element
+            self.evaluate_op("rot")?; // rotate stack
+            self.call_quote(&quote)?; // execute anonymous function
+        }
+
+        Ok(())
+    }
+
+    // list [ q ] reduce -> value
+    fn c_reduce(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let mut list = self.pop_stack_list()?.into_iter();
+
+        let Some(mut acc) = list.next() else {
+            return Err(CompError::InvalidArgument {
+                op: op.to_string(),
+                reason: String::from("list is empty"),
+            });
+        };
+        for x in list {
+            acc = self.apply_quote_once(&quote, vec![acc, x])?;
+        }
+        self.stack.push(acc);
+
+        Ok(())
+    }
+
+    // list list [ q ] zip -> list ( the longer list is truncated )
+    fn c_zip(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(3, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let b: Vec<Value> = self.pop_stack_list()?;
+        let a: Vec<Value> = self.pop_stack_list()?;
+
+        let mut zipped: Vec<Value> = vec![];
+        for (x, y) in a.into_iter().zip(b) {
+            zipped.extend(self.apply_quote(&quote, vec![x, y])?);
+        }
+        self.stack.push(Value::List(zipped));
+
+        Ok(())
+    }
+
+    // list [ q ] each -> ( results left on stack )
+    fn c_each(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+
+        for x in self.pop_stack_list()? {
+            self.stack.push(x);
+            self.call_quote(&quote)?;
+        }
+
+        Ok(())
+    }
+
+    /*
+        the commands below apply the quotation to the elements of the list
+        below it or, if there is no list, to the elements of the stack. the
+        result is a list or replaces the stack.
+    */
+
+    // [ q ] filter -> elements for which the quotation is true
+    fn c_filter(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let (elements, list) = self.elements()?;
+
+        let mut kept: Vec<Value> = vec![];
+        for x in elements {
+            if self.apply_quote_once(&quote, vec![x.clone()])?.to_bool()? {
+                kept.push(x);
+            }
+        }
+        self.set_elements(kept, list);
+
+        Ok(())
+    }
+
+    // [ q ] take_while -> leading elements for which the quotation is true
+    fn c_take_while(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let (elements, list) = self.elements()?;
+
+        let mut kept: Vec<Value> = vec![];
+        for x in elements {
+            if !self.apply_quote_once(&quote, vec![x.clone()])?.to_bool()? {
+                break;
+            }
+            kept.push(x);
+        }
+        self.set_elements(kept, list);
+
+        Ok(())
+    }
+
+    // [ q ] sort -> elements ordered by the numeric key the quotation returns
+    // for each element ( [ ] sorts by value )
+    fn c_sort(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let (elements, list) = self.elements()?;
+
+        let mut keyed: Vec<(Value, Value)> = vec![];
+        for x in elements {
+            let key: Value = self.apply_quote_once(&quote, vec![x.clone()])?;
+            key.to_f64()?;
+            keyed.push((key, x));
+        }
+        keyed.sort_by(|(a, _), (b, _)| a.num_cmp(b).unwrap_or(Ordering::Equal));
+        self.set_elements(keyed.into_iter().map(|(_, x)| x).collect(), list);
+
+        Ok(())
+    }
+
+    // unique -> elements without repeats ( the first of equal elements is kept )
+    fn c_unique(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let (elements, list) = self.elements()?;
+
+        let mut kept: Vec<Value> = vec![];
+        for x in elements {
+            if !kept.iter().any(|k| Self::equal(k, &x)) {
+                kept.push(x);
+            }
+        }
+        self.set_elements(kept, list);
+
+        Ok(())
+    }
+
+    // [ q ] group -> lists of elements with equal keys ( in order of first
+    // appearance )
+    fn c_group(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let (elements, list) = self.elements()?;
+
+        let mut groups: Vec<(Value, Vec<Value>)> = vec![];
+        for x in elements {
+            let key: Value = self.apply_quote_once(&quote, vec![x.clone()])?;
+            match groups.iter_mut().find(|(k, _)| Self::equal(k, &key)) {
+                Some((_, group)) => group.push(x),
+                None => groups.push((key, vec![x])),
+            }
+        }
+        let groups: Vec<Value> = groups
+            .into_iter()
+            .map(|(_, group)| Value::List(group))
+            .collect();
+        self.set_elements(groups, list);
+
+        Ok(())
+    }
+
+    // [ q ] count_if -> number of elements for which the quotation is true
+    fn c_count_if(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let (elements, list) = self.elements()?;
+
+        let mut count: u64 = 0;
+        for x in elements {
+            if self.apply_quote_once(&quote, vec![x])?.to_bool()? {
+                count += 1;
+            }
+        }
+        self.set_result(Value::from(count), list);
+
+        Ok(())
+    }
+
+    // [ q ] find -> first element for which the quotation is true
+    fn c_find(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let (elements, list) = self.elements()?;
+
+        for x in elements {
+            if self.apply_quote_once(&quote, vec![x.clone()])?.to_bool()? {
+                self.set_result(x, list);
+                return Ok(());
+            }
+        }
+
+        Err(CompError::InvalidArgument {
+            op: op.to_string(),
+            reason: format!("no element satisfies {quote}"),
+        })
+    }
+
+    // [ q ] partition -> list of elements for which the quotation is true and
+    // list of the others
+    fn c_partition(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let (elements, list) = self.elements()?;
+
+        let (mut yes, mut no): (Vec<Value>, Vec<Value>) = (vec![], vec![]);
+        for x in elements {
+            match self.apply_quote_once(&quote, vec![x.clone()])?.to_bool()? {
+                true => yes.push(x),
+                false => no.push(x),
+            }
+        }
+        if !list {
+            self.stack.clear();
+        }
+        self.stack.push(Value::List(yes));
+        self.stack.push(Value::List(no));
+
+        Ok(())
+    }
+
+    fn c_call(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+
+        self.call_quote(&quote)
+    }
+
+    // x [ q ] dip -> q x
+    fn c_dip(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let x: Value = self.pop_stack_literal();
+
+        self.call_quote(&quote)?;
+        self.stack.push(x);
+
+        Ok(())
+    }
+
+    // x [ q ] keep -> x q x
+    fn c_keep(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let quote: Arc<Quote> = self.pop_stack_quote()?;
+        let x: Value = self.pop_stack_literal();
+
+        self.stack.push(x.clone());
+        self.call_quote(&quote)?;
+        self.stack.push(x);
+
+        Ok(())
+    }
+
+    // x [ p ] [ q ] bi -> x p x q
+    fn c_bi(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(3, op)?;
+
+        let q: Arc<Quote> = self.pop_stack_quote()?;
+        let p: Arc<Quote> = self.pop_stack_quote()?;
+        let x: Value = self.pop_stack_literal();
+
+        self.stack.push(x.clone());
+        self.call_quote(&p)?;
+        self.stack.push(x);
+        self.call_quote(&q)
+    }
+
+    // [ p ] [ q ] compose -> [ p q ]
+    fn c_compose(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let q: Arc<Quote> = self.pop_stack_quote()?;
+        let p: Arc<Quote> = self.pop_stack_quote()?;
+
+        let fops: Vec<String> = [p.fops(), q.fops()].concat();
+        let body: Block = p.body().iter().chain(q.body().iter()).cloned().collect();
+        self.stack
+            .push(Value::Quote(Arc::new(Quote::new(fops, body))));
+
+        Ok(())
+    }
+
+    // x [ q ] curry -> [ x q ]
+    fn c_curry(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(2, op)?;
+
+        let q: Arc<Quote> = self.pop_stack_quote()?;
+        let x: Value = self.pop_stack_literal();
+
+        let fops: Vec<String> = [&[x.source()], q.fops()].concat();
+        let body: Block = std::iter::once(Node::Push(x))
+            .chain(q.body().iter().cloned())
+            .collect();
+        self.stack
+            .push(Value::Quote(Arc::new(Quote::new(fops, body))));
+
+        Ok(())
+    }
+
+    // element at depth ( 0 is the top of the stack ) is a list
+    fn has_list(&self, depth: usize) -> bool {
+        matches!(
+            self.stack.iter().rev().nth(depth).map(Value::inner),
+            Some(Value::List(_))
+        )
+    }
+
+    // elements of the list on top of the stack ( removed ) or of the whole
+    // stack ( left in place until the result replaces it )
+    fn elements(&mut self) -> Result<(Vec<Value>, bool), CompError> {
+        match self.has_list(0) {
+            true => Ok((self.pop_stack_list()?, true)),
+            false => Ok((self.stack.clone(), false)),
+        }
+    }
+
+    // push elements as a list or replace the stack with them
+    fn set_elements(&mut self, elements: Vec<Value>, list: bool) {
+        match list {
+            true => self.stack.push(Value::List(elements)),
+            false => self.stack = elements,
+        }
+    }
+
+    // push result of list or replace the stack with it
+    fn set_result(&mut self, result: Value, list: bool) {
+        if !list {
+            self.stack.clear();
+        }
+        self.stack.push(result);
+    }
+
+    // call quotation on arguments and return the elements it leaves on the
+    // stack
+    fn apply_quote(&mut self, quote: &Quote, args: Vec<Value>) -> Result<Vec<Value>, CompError> {
+        let depth: usize = self.stack.len();
+
+        self.stack.extend(args);
+        self.call_quote(quote)?;
+
+        Ok(self.stack.split_off(depth.min(self.stack.len())))
+    }
+
+    // call quotation on arguments and return its result
+    fn apply_quote_once(&mut self, quote: &Quote, args: Vec<Value>) -> Result<Value, CompError> {
+        self.apply_quote(quote, args)?
+            .pop()
+            .ok_or_else(|| CompError::InvalidArgument {
+                op: quote.to_string(),
+                reason: String::from("quotation did not return a value"),
+            })
+    }
+
+    // call quotation ( with its own local variables )
+    fn call_quote(&mut self, quote: &Quote) -> Result<(), CompError> {
+        let depth: usize = self.calls.len();
+        self.enter(&quote.to_string(), false);
+        let result = match self.config.compile {
+            true => self.run_code(quote.code(&self.cmdmap, self.natives)),
+            false => self.evaluate_block(quote.body().clone()),
+        };
+
+        self.leave_call(depth, result)
+    }
+
+    /* ---- configuration --------------------------------------------------- */
+
+    fn c_save_config(&mut self, _op: &str) -> Result<(), CompError> {
+        // save configuration to file
+        self.save_config("comp.toml");
+
+        Ok(())
+    }
+
+    fn c_print_config(&mut self, _op: &str) -> Result<(), CompError> {
+        // print current configuration
+        println!("{}", self.config,);
+
+        Ok(())
+    }
+
+    /* ---- session --------------------------------------------------------- */
+
+    fn c_show_mem(&mut self, _op: &str) -> Result<(), CompError> {
+        let mut names: Vec<&String> = self.mem.keys().collect();
+        names.sort_unstable();
+
+        for name in names {
+            println!(
+                "  {} {}",
+                self.theme.green_eggs_bold(name),
+                self.theme.blue_smurf(&self.mem[name].to_string()),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn c_show_fns(&mut self, _op: &str) -> Result<(), CompError> {
+        for f in &self.fns {
+            println!(
+                "  {} {} {} {}",
+                self.theme.grey_mouse("("),
+                self.theme.green_eggs_bold(&f.name),
+                self.theme.blue_smurf(&f.fops.join(" ")),
+                self.theme.grey_mouse(")"),
+            );
+        }
+
+        Ok(())
+    }
+
+    // remove memory or function ( the name is read from the operations list
+    // rather than the stack so that memory names are not evaluated )
+    fn forget(&mut self, op: &str, name: &str) {
+        let forgot_mem: bool = self.mem.remove(name).is_some();
+        let forgot_fn: bool = match self.is_user_function(name) {
+            Some(index) => {
+                self.fns.remove(index);
+                self.code.remove(name);
+                true
+            }
+            None => false,
+        };
+
+        if !(forgot_mem || forgot_fn) && self.config.show_warnings {
+            eprintln!(
+                "  {}: [{}] operation called with unknown name [{}]",
+                self.theme.yellow_canary_bold("warning"),
+                self.theme.blue_coffee_bold(op),
+                self.theme.blue_coffee_bold(name),
+            );
+        }
+    }
+
+    fn c_reset_mem(&mut self, _op: &str) -> Result<(), CompError> {
+        self.mem.clear();
+
+        Ok(())
+    }
+
+    fn c_reset_fns(&mut self, _op: &str) -> Result<(), CompError> {
+        self.fns.clear();
+        self.code.clear();
+
+        Ok(())
+    }
+
+    fn c_reset(&mut self, _op: &str) -> Result<(), CompError> {
+        self.stack.clear();
+        self.mem.clear();
+        self.fns.clear();
+        self.code.clear();
+
+        Ok(())
+    }
+
+    /* ---- output ---------------------------------------------------------- */
+
+    fn c_peek(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        println!(
+            "  {}",
+            self.theme
+                .white(&self.display(&self.stack[self.stack.len() - 1])),
+        );
+
+        Ok(())
+    }
+
+    fn c_print(&mut self, op: &str) -> Result<(), CompError> {
+        self.check_stack_error(1, op)?;
+
+        let out = self.pop_stack_string()?;
+
+        println!("  {}", self.theme.grey_mouse(&out),);
+
+        Ok(())
+    }
+
+    /// Stack elements as they are displayed ( integers are shown in the
+    /// display radix ).
+    pub fn display_stack(&self) -> Vec<String> {
+        self.stack.iter().map(|a| self.display(a)).collect()
+    }
+
+    fn display(&self, value: &Value) -> String {
+        match value.inner() {
+            Value::Int(i) => self.display_int(i),
+            Value::List(l) => {
+                let items: Vec<String> = l.iter().map(|a| self.display(a)).collect();
+                format!("({})", items.join(" "))
+            }
+            Value::Quantity(magnitude, unit) => format!("{} {unit}", self.display(magnitude)),
+            _ => value.to_string(),
+        }
+    }
+
+    // integer in the display radix with its radix prefix ( negative integers
+    // are shown in two's complement in programmer mode )
+    fn display_int(&self, i: &BigInt) -> String {
+        let radix: u32 = match self.config.radix {
+            2 | 8 | 16 => self.config.radix,
+            _ => return Self::group_digits(&i.to_string(), 3, self.config.digit_grouping),
+        };
+        let (sign, digits): (&str, BigInt) = match (i.is_negative(), self.config.word_size) {
+            (true, 0) => ("-", i.abs()),
+            (true, bits) => ("", i & BigInt::from(Self::word_mask(bits))),
+            (false, _) => ("", i.clone()),
+        };
+        let prefix: &str = match radix {
+            16 => "0x",
+            8 => "0o",
+            _ => "0b",
+        };
+        let group: usize = if radix == 8 { 3 } else { 4 };
+
+        format!(
+            "{sign}{prefix}{}",
+            Self::group_digits(
+                &digits.to_str_radix(radix),
+                group,
+                self.config.digit_grouping
+            )
+        )
+    }
+
+    // digits separated into groups of n by underscores ( counted from the
+    // right and after any sign )
+    fn group_digits(digits: &str, n: usize, grouping: bool) -> String {
+        let (sign, digits): (&str, &str) = match digits.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", digits),
+        };
+        if !grouping {
+            return format!("{sign}{digits}");
+        }
+
+        let len: usize = digits.len();
+        digits
+            .chars()
+            .enumerate()
+            .fold(String::from(sign), |mut grouped, (i, c)| {
+                if i > 0 && (len - i).is_multiple_of(n) {
+                    grouped.push('_');
+                }
+                grouped.push(c);
+                grouped
+            })
+    }
+
+    // support functions -------------------------------------------------------
+
+    fn is_user_function(&self, op: &str) -> Option<usize> {
+        // is operator a user defined function?
+        if !self.fns.is_empty() {
+            for (i, f) in self.fns.iter().enumerate() {
+                if f.name == op {
+                    return Some(i);
+                }
+                // return function index
+            }
+        }
+        None
+    }
+
+    fn is_user_memory(&self, op: &str) -> Option<Value> {
+        // is operator a user defined memory item?
+        if self.mem.contains_key(op) {
+            return Some(self.mem[op].clone());
+            // return stored memory value
+        }
+        None
+    }
+
+    // factorial
+    pub fn factorial(n: u64) -> BigUint {
+        (1..=n).map(BigUint::from).product()
+    }
+
+    // greatest common divisor
+    pub fn gcd(a: BigUint, b: BigUint) -> BigUint {
+        match b.is_zero() {
+            true => a,
+            false => {
+                let r: BigUint = &a % &b;
+                Self::gcd(b, r)
+            }
+        }
+    }
+
+    // save configuration file to home folder
+    fn save_config(&self, filename: &str) {
+        let filename: String = filename.to_string();
+
+        let home_folder: String = match home::home_dir() {
+            Some(dir) => dir.to_str().unwrap().to_string(),
+            _ => String::from(""),
+        };
+
+        let config_filename: String = format!("{}/{}", home_folder, filename);
+
+        let path: &Path = Path::new(&config_filename);
+
+        let config_data: String = toml::to_string(&self.config).unwrap();
+
+        match fs::write(path, config_data) {
+            Ok(_) => {
+                println!(
+                    "  configuration file [{}] saved",
+                    self.theme.blue_smurf_bold(CONFIG_FILE),
+                );
+            }
+            Err(error) => {
+                eprintln!(
+                    "  {}: configuration file [{}] could not be saved: {}",
+                    self.theme.red_bold("error"),
+                    self.theme.blue_smurf_bold(CONFIG_FILE),
+                    error,
+                );
+            }
+        }
+    }
+
+    // load configuration file from home folder
+    pub fn load_config(&mut self) {
+        /*
+            println!(
+                "  reading configuration file [{}]",
+                self.theme.blue_coffee_bold(filename),
+            );
+        */
+
+        // read file contents
+        let filename: String = CONFIG_FILE.to_string();
+
+        let home_folder: String = match home::home_dir() {
+            Some(dir) => dir.to_str().unwrap().to_string(),
+            _ => String::from(""),
+        };
+
+        let config_filename: String = format!("{}/{}", home_folder, filename);
+
+        let path: &Path = Path::new(&config_filename);
+
+        if let Ok(config_file_toml) = fs::read_to_string(path) {
+            // read file success
+            // deserialize configuration TOML and update configuration
+            let cfg: Config = match toml::from_str(&config_file_toml) {
+                Ok(c) => c,
+                Err(_) => {
+                    // parse fail
+                    if self.config.show_warnings {
+                        eprintln!(
+                            "  {}: configuration file [{}] (ignored) has been corrupted or \
+                            is improperly constructed for this version of comp",
+                            self.theme.yellow_canary_bold("warning"),
+                            self.theme.blue_smurf_bold(CONFIG_FILE),
+                        );
+                    }
+                    Config::new()
+                }
+            };
+
+            self.config = cfg;
+        }
+
+        self.register_conversions();
+    }
+
+    /// Load the prelude file from the home folder ( if enabled in the
+    /// configuration and present ). Prelude operations are evaluated before
+    /// any queued operations.
+    pub fn load_prelude(&mut self) -> Result<(), CompError> {
+        if !self.config.prelude {
+            return Ok(());
+        }
+
+        let path: PathBuf = match home::home_dir() {
+            Some(dir) => dir.join(&self.config.prelude_file),
+            _ => return Ok(()),
+        };
+
+        match fs::canonicalize(path) {
+            Ok(path) if path.is_file() && !self.imported.contains(&path) => {
+                let origin: String = self.config.prelude_file.clone();
+                let fns: Vec<Function> = self.fns.clone();
+                self.evaluate_module(path, &origin)?;
+
+                // remember prelude functions ( they are not saved with the
+                // session )
+                for f in &self.fns {
+                    if !fns.iter().any(|g| g.name == f.name && g.fops == f.fops) {
+                        self.prelude_fns.insert(f.name.clone(), f.fops.clone());
+                    }
+                }
+
+                Ok(())
+            }
+            _ => Ok(()), // no prelude
+        }
+    }
+
+    // session snapshot ( functions defined by the prelude are left out as the
+    // prelude defines them again )
+    fn snapshot(&self) -> Snapshot {
+        let fns: Vec<Function> = self
+            .fns
+            .iter()
+            .filter(|f| self.prelude_fns.get(&f.name) != Some(&f.fops))
+            .cloned()
+            .collect();
+
+        Snapshot {
+            version: SNAPSHOT_VERSION,
+            stack: self.stack.clone(),
+            mem: self.mem.clone().into_iter().collect(),
+            fns,
+        }
+    }
+
+    // save session snapshot ( stack, memory, and functions ) to home folder
+    // for later use (persistence)
+    pub fn save_stack(&self) {
+        let home_folder: String = match home::home_dir() {
+            Some(dir) => dir.to_str().unwrap().to_string(),
+            _ => String::from(""),
+        };
+
+        let config_filename: String = format!("{}/{}", home_folder, PERSISTENCE_FILE);
+
+        let path: &Path = Path::new(&config_filename);
+
+        let stack_data: String = serde_yaml::to_string(&self.snapshot()).unwrap();
+
+        match fs::write(path, stack_data) {
+            Ok(_) => {
+                /*
+                println!(
+                    "  stack snapshot [{}] saved",
+                    self.theme.blue_smurf_bold(PERSISTENCE_FILE),
+                );
+                */
+            }
+            Err(error) => {
+                eprintln!(
+                    "  {}: stack snapshot [{}] could not be saved: {}",
+                    self.theme.red_bold("error"),
+                    self.theme.blue_smurf_bold(PERSISTENCE_FILE),
+                    error,
+                );
+            }
+        }
+    }
+
+    // load session snapshot from home folder
+    pub fn load_stack(&mut self) {
+        let home_folder: String = match home::home_dir() {
+            Some(dir) => dir.to_str().unwrap().to_string(),
+            _ => String::from(""),
+        };
+
+        let config_filename: String = format!("{}/{}", home_folder, PERSISTENCE_FILE);
+
+        let path: &Path = Path::new(&config_filename);
+
+        if let Ok(stack_file_yaml) = fs::read_to_string(path) {
+            // read file success
+            // deserialize snapshot YAML and load
+            match serde_yaml::from_str::<Snapshot>(&stack_file_yaml) {
+                Ok(snapshot) if snapshot.version <= SNAPSHOT_VERSION => {
+                    self.stack = snapshot.stack;
+                    self.mem = snapshot.mem.into_iter().collect();
+                    self.restore_functions(snapshot.fns);
+                }
+                Ok(_) => {
+                    // snapshot from later version
+                    if self.config.show_warnings {
+                        eprintln!(
+                            "  {}: stack snapshot [{}] (ignored) was saved by a newer \
+                            version of comp",
+                            self.theme.yellow_canary_bold("warning"),
+                            self.theme.blue_smurf_bold(PERSISTENCE_FILE),
+                        );
+                    }
+                }
+                // stack snapshot from earlier version ( stack only )
+                Err(_) if serde_yaml::from_str::<Vec<Value>>(&stack_file_yaml).is_ok() => {
+                    self.stack = serde_yaml::from_str(&stack_file_yaml).unwrap();
+                }
+                // stack snapshot from earlier version ( untyped )
+                Err(_) if serde_yaml::from_str::<Vec<String>>(&stack_file_yaml).is_ok() => {
+                    let s: Vec<String> = serde_yaml::from_str(&stack_file_yaml).unwrap();
+                    self.stack = s.iter().map(|op| Value::parse(op)).collect();
+                }
+                Err(_) => {
+                    // parse fail
+                    if self.config.show_warnings {
+                        eprintln!(
+                            "  {}: stack snapshot [{}] (ignored) has been corrupted or \
+                            is improperly constructed for this version of comp",
+                            self.theme.yellow_canary_bold("warning"),
+                            self.theme.blue_smurf_bold(PERSISTENCE_FILE),
+                        );
+                    }
+                }
+            };
+        }
+    }
+
+    // parse and compile functions loaded from a stack snapshot ( functions
+    // that no longer parse are dropped with a warning )
+    fn restore_functions(&mut self, fns: Vec<Function>) {
+        self.fns.clear();
+        self.code.clear();
+
+        for mut f in fns {
+            match lexer::lex(&f.fops.join(" "), PERSISTENCE_FILE).and_then(parser::parse) {
+                Ok(nodes) => {
+                    f.body = Block::from(nodes);
+                    self.code
+                        .insert(f.name.clone(), bytecode::compile(&f.body, &self.cmdmap));
+                    self.fns.push(f);
+                }
+                Err(error) => {
+                    if self.config.show_warnings {
+                        eprintln!(
+                            "  {}: function [{}] in stack snapshot [{}] (ignored) could not \
+                            be parsed: {error}",
+                            self.theme.yellow_canary_bold("warning"),
+                            self.theme.blue_smurf_bold(&f.name),
+                            self.theme.blue_smurf_bold(PERSISTENCE_FILE),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn output_rgb_dec(&self, color: cor::Color) -> String {
+        format!(
+            "{} {} {}",
+            self.theme.color_rgb(&color.r.to_string(), &color,),
+            self.theme.color_rgb(
+                &color.g.to_string(),
+                &cor::Color {
+                    r: color.r,
+                    g: color.g,
+                    b: color.b,
+                    bold: color.bold,
+                },
+            ),
+            self.theme.color_rgb(&color.b.to_string(), &color,),
+        )
+    }
+
+    fn _output_rgb_hex(&self, color: cor::Color) -> String {
+        format!(
+            "{}",
+            self.theme.color_rgb(
+                &format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b),
+                &color,
+            ),
+        )
+    }
+
+    fn output_rgb_hex_bg(&self, color: cor::Color) -> String {
+        format!(
+            "{}",
+            self.theme.color_rgb_bg(
+                &format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b),
+                &color,
+            ),
+        )
+    }
+
+    /// Names of all registered native commands.
+    pub fn get_cmds(&self) -> Vec<String> {
+        self.cmdmap.keys().cloned().collect()
+    }
+
+    /// Names of all user-defined functions.
+    pub fn get_fns(&self) -> Vec<String> {
+        self.fns.iter().map(|f| f.name.clone()).collect()
+    }
+
+    /// Names of all user memory items.
+    pub fn get_mem(&self) -> Vec<String> {
+        self.mem.keys().cloned().collect()
+    }
+
+    /// Copy of the current stack. Prefer [`Interpreter::stack`].
+    pub fn get_stack(&self) -> Vec<Value> {
+        self.stack.clone()
+    }
+}
+
+/* unit tests --------------------------------------------------------------- */
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    #[test]
+    fn test_interpreter() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&8.to_string());
+        comp.push_op("io");
+        comp.push_op("prod");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_i64().unwrap() == 40320);
+    }
+
+    #[test]
+    fn test_core() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+
+        comp.push_op("rot");
+        comp.push_op("rot");
+        comp.push_op("roll");
+        comp.push_op("roll");
+
+        comp.push_op("deg_rad");
+        comp.push_op("cos");
+        comp.push_op("acos");
+        comp.push_op("sin");
+        comp.push_op("asin");
+        comp.push_op("tan");
+        comp.push_op("atan");
+        comp.push_op("rad_deg");
+        comp.push_op("round");
+        comp.push_op("roll");
+        comp.push_op("roll");
+        comp.push_op("roll");
+        comp.push_op("roll");
+        comp.push_op("dup");
+        comp.push_op("drop");
+        comp.push_op("swap");
+        comp.push_op("swap");
+        comp.push_op("+");
+        comp.push_op("-");
+        comp.push_op("/");
+
+        comp.push_op(&10.to_string());
+        comp.push_op("log2");
+        comp.push_op(&10.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op("logn");
+        comp.push_op("-");
+        comp.push_op("round");
+        comp.push_op("+");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == -0.2);
+    }
+
+    #[test]
+    fn test_support() {
+        assert!(Interpreter::gcd(55u32.into(), 10u32.into()) == 5u32.into());
+        assert!(Interpreter::factorial(10) == 3628800u32.into());
+    }
+
+    #[test]
+    fn test_roots() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&2.to_string());
+        comp.push_op("dup");
+        comp.push_op("sqrt");
+        comp.push_op("swap");
+        comp.push_op(&32.to_string());
+        comp.push_op("^");
+        comp.push_op(&(32. * 2.).to_string());
+        comp.push_op("nroot");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == comp.pop_stack_f64().unwrap());
+
+        comp.push_op(&1.to_string());
+        comp.push_op(&(-2).to_string());
+        comp.push_op("chs");
+        comp.push_op("chs");
+        comp.push_op("pi");
+        comp.push_op("x");
+        comp.push_op("pi");
+        comp.push_op(&2.to_string());
+        comp.push_op("^");
+        comp.push_op(&1.to_string());
+        comp.push_op("+");
+        comp.push_op("proot");
+        comp.push_op("sum");
+        comp.push_op(&2.to_string());
+        comp.push_op("/");
+        comp.push_op("pi");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == comp.pop_stack_f64().unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cls() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op("cls");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == 0.);
+    }
+
+    #[test]
+    fn test_api() {
+        let mut comp = Interpreter::new();
+
+        comp.push_ops(["3", "4", "+"]);
+        comp.push_op("dup");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.stack() == [Value::from(7i64), Value::from(7i64)]);
+        assert!(comp.peek() == Some(&Value::from(7i64)));
+
+        fn c_double(comp: &mut Interpreter, op: &str) -> Result<(), CompError> {
+            comp.check_stack_error(1, op)?;
+
+            let a = comp.pop_stack_f64()?;
+            comp.push(Value::Float(2. * a));
+
+            Ok(())
+        }
+
+        comp.build_native("double", c_double);
+        comp.push_ops(["double"]);
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop() == Some(Value::Float(14.)));
+        assert!(comp.get_cmds().contains(&String::from("double")));
+
+        fn c_negate(comp: &mut Interpreter, op: &str) -> Result<(), CompError> {
+            comp.check_stack_error(1, op)?;
+
+            let a = comp.pop_stack_f64()?;
+            comp.push(Value::Float(-a));
+
+            Ok(())
+        }
+
+        // natives registered ( or replaced ) after a function or quotation is
+        // compiled
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops("( quad double double ) [ double ] q store 3 quad".split_whitespace());
+            comp.evaluate_ops().unwrap();
+
+            comp.build_native("double", c_double);
+            comp.push_ops("3 quad 3 q call".split_whitespace());
+            comp.evaluate_ops().unwrap();
+
+            comp.build_native("double", c_negate);
+            comp.push_ops("3 q call 3 quad".split_whitespace());
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0][1] == Value::from("double"));
+        assert!(stacks[0][3..] == [12., 6., -3., 3.].map(Value::Float));
+    }
+
+    #[test]
+    fn test_values() {
+        let mut comp = Interpreter::new();
+
+        comp.push_ops(["8", "2", "/", "7", "2", "/", "3", "4", "lt", "hello"]);
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop() == Some(Value::Str(String::from("hello"))));
+        assert!(comp.pop() == Some(Value::Bool(true)));
+        assert!(comp.pop() == Some(Value::parse("7/2")));
+        assert!(comp.pop() == Some(Value::from(4i64)));
+
+        comp.push_ops(["2", "100", "^", "0.5", "2", "x"]);
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop() == Some(Value::Float(1.)));
+        assert!(comp.pop() == Some(Value::Int(BigInt::from(1) << 100)));
+    }
+
+    #[test]
+    fn test_bigint() {
+        let mut comp = Interpreter::new();
+
+        comp.push_ops([
+            "30", "!", "2", "200", "^", "2", "64", "^", "dup", "1", "+", "eq",
+        ]);
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop() == Some(Value::Bool(false)));
+        assert!(
+            comp.pop_stack_string().unwrap()
+                == "1606938044258990275541962092341162602522202993782792835301376"
+        );
+        assert!(comp.pop_stack_string().unwrap() == "265252859812191058636308480000000");
+
+        comp.push_ops([
+            "2", "100", "^", "1", "-", "2", "70", "^", "1", "-", "and", "ones",
+        ]);
+        comp.push_ops(["12345678901234567890123", "9876543210", "gcd"]);
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop() == Some(Value::from(3i64)));
+        assert!(comp.pop() == Some(Value::from(70i64)));
+    }
+
+    #[test]
+    fn test_rational() {
+        let mut comp = Interpreter::new();
+
+        comp.push_ops(["1", "2", "3", "4", "avg_all", "3", "inv", "3", "x"]);
+        comp.push_ops([
+            "1/3", "1/6", "+", "2/3", "-2", "^", "1/3", "0.3", "gt", "7/2", "floor",
+        ]);
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop() == Some(Value::from(3i64)));
+        assert!(comp.pop() == Some(Value::Bool(true)));
+        assert!(comp.pop_stack_string().unwrap() == "9/4");
+        assert!(comp.pop_stack_string().unwrap() == "1/2");
+        assert!(comp.pop() == Some(Value::from(1i64)));
+        assert!(comp.pop_stack_string().unwrap() == "5/2");
+
+        comp.push_ops(["1/8", "float", "1/3", "2", "/"]);
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_string().unwrap() == "1/6");
+        assert!(comp.pop() == Some(Value::Float(0.125)));
+    }
+
+    #[test]
+    fn test_complex() {
+        let mut comp = Interpreter::new();
+
+        comp.push_ops(["1", "2", "5", "proot", "3+4i", "abs", "1+2i", "3-1i", "x"]);
+        comp.push_ops([
+            "1+2i", "dup", "conj", "+", "-4i", "im", "3+4i", "polar", "rect", "re",
+        ]);
+
+        comp.evaluate_ops().unwrap();
+
+        assert!((comp.pop_stack_f64().unwrap() - 3.).abs() < 1e-12);
+        assert!(comp.pop() == Some(Value::Float(-4.)));
+        assert!(comp.pop() == Some(Value::Float(2.)));
+        assert!(comp.pop() == Some(Value::Complex(Complex64::new(5., 5.))));
+        assert!(comp.pop() == Some(Value::Float(5.)));
+        assert!(comp.pop() == Some(Value::parse("-1-2i")));
+        assert!(comp.pop() == Some(Value::parse("-1+2i")));
+    }
+
+    #[test]
+    fn test_import() {
+        let dir: PathBuf = env::temp_dir().join(format!("comp_import_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.cm"), "b.cm import ( inc 1 + )").unwrap();
+        fs::write(dir.join("b.cm"), "( dbl 2 x )").unwrap();
+        fs::write(dir.join("c.cm"), "d.cm import").unwrap();
+        fs::write(dir.join("d.cm"), "c.cm import").unwrap();
+
+        let mut comp = Interpreter::new();
+        comp.set_script_path(&dir.join("main.cm"));
+
+        comp.push_ops(["a.cm", "import", "a.cm", "use", "3", "inc", "dbl"]);
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.stack() == [Value::from(8i64)]);
+
+        comp.push_ops(["c.cm", "import"]);
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::InvalidArgument { .. })
+        ));
+
+        comp.clear_ops();
+        comp.push_ops(["missing.cm", "import"]);
+
+        assert!(matches!(
+            comp.evaluate_ops(),
+            Err(CompError::UnknownFile { .. })
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_scripts() {
+        let dir: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "cm"))
+            .collect();
+        paths.sort();
+        assert!(!paths.is_empty());
+
+        // every script leaves its result on the stack ( in place of printing
+        // it ) with functions compiled and evaluated from the parsed operations
+        for path in paths {
+            let name: String = path.file_stem().unwrap().to_string_lossy().to_string();
+            let source: String = fs::read_to_string(&path).unwrap();
+            let source: &str = source.trim_end().strip_suffix("print").unwrap();
+
+            let mut stacks: Vec<Vec<Value>> = vec![];
+            for compile in [true, false] {
+                let mut comp = Interpreter::new();
+                comp.config.compile = compile;
+                comp.set_script_path(&path);
+                comp.push_source(source, &path.display().to_string())
+                    .unwrap();
+
+                comp.evaluate_ops().unwrap();
+
+                stacks.push(comp.get_stack());
+            }
+
+            assert!(stacks[0] == stacks[1], "{name}");
+            assert!(
+                stacks[0].last() == Some(&Value::from(format!("pass..{name}"))),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_prelude() {
+        let path: PathBuf = env::temp_dir().join(format!("comp_prelude_{}.cm", std::process::id()));
+        fs::write(&path, "( square dup x )").unwrap();
+
+        let mut comp = Interpreter::new();
+        comp.config.prelude_file = path.display().to_string();
+
+        comp.push_ops(["5", "square"]);
+        comp.load_prelude().unwrap();
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.stack() == [Value::from(25i64)]);
+
+        // prelude functions are not saved with the session unless redefined
+        let saved = |comp: &Interpreter| -> Vec<String> {
+            comp.snapshot().fns.into_iter().map(|f| f.name).collect()
+        };
+        comp.push_ops("( cube dup square x )".split_whitespace());
+        comp.evaluate_ops().unwrap();
+
+        assert!(saved(&comp) == ["cube"]);
+
+        comp.push_ops("( square 2 ^ )".split_whitespace());
+        comp.evaluate_ops().unwrap();
+
+        assert!(saved(&comp) == ["cube", "square"]);
+
+        let mut comp = Interpreter::new();
+        comp.config.prelude_file = path.display().to_string();
+        comp.config.prelude = false;
+
+        comp.push_ops(["5", "square"]);
+        comp.load_prelude().unwrap();
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop() == Some(Value::from("square")));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_session() {
+        let mut comp = Interpreter::new();
+
+        comp.push_ops(
+            "( inc 1 + ) ( dbl 2 x ) ( inc 10 + ) 1/3 a store 4 b store".split_whitespace(),
+        );
+        comp.push_ops("1 inc dbl forget dbl forget b dbl".split_whitespace());
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop() == Some(Value::from("dbl")));
+        assert!(comp.pop() == Some(Value::from(22i64)));
+        assert!(comp.get_mem() == ["a"]);
+        assert!(comp.get_fns() == ["inc"]);
+
+        let yaml: String = serde_yaml::to_string(&Snapshot {
+            version: SNAPSHOT_VERSION,
+            stack: vec![Value::from(5i64)],
+            mem: comp.mem.clone().into_iter().collect(),
+            fns: comp.fns.clone(),
+        })
+        .unwrap();
+        let snapshot: Snapshot = serde_yaml::from_str(&yaml).unwrap();
+
+        assert!(snapshot.stack == [Value::from(5i64)]);
+        assert!(snapshot.mem["a"] == Value::parse("1/3"));
+        assert!(snapshot.fns[0].fops == ["10", "+"]);
+
+        let mut broken: Vec<Function> = snapshot.fns.clone();
+        broken.push(Function {
+            name: String::from("bad"),
+            fops: vec![String::from("1"), String::from("if")],
+            body: Block::default(),
+        });
+        comp.restore_functions(broken);
+
+        assert!(comp.get_fns() == ["inc"]);
+        assert!(comp.code.contains_key("inc") && !comp.code.contains_key("bad"));
+
+        comp.push_ops(["7", "reset"]);
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.stack().is_empty() && comp.get_mem().is_empty() && comp.get_fns().is_empty());
+    }
+
+    #[test]
+    fn test_control() {
+        let mut comp = Interpreter::new();
+
+        comp.push_ops("1 2 lt if 10 else 20 fi 2 1 lt if 30 else 40 fi".split_whitespace());
+        comp.push_ops("5 times idx loop + + + +".split_whitespace());
+        comp.push_ops("0 10 0 do idx 2 % if continue fi idx + loop".split_whitespace());
+        comp.push_ops("1 begin 2 x dup 1000 gt until".split_whitespace());
+        comp.push_ops("1 begin dup 100 lt while 3 x repeat".split_whitespace());
+        comp.push_ops("0 begin 1 + dup 7 eq if break fi repeat".split_whitespace());
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop() == Some(Value::from(7i64)));
+        assert!(comp.pop() == Some(Value::from(243i64)));
+        assert!(comp.pop() == Some(Value::from(1024i64)));
+        assert!(comp.pop() == Some(Value::from(20i64)));
+        assert!(comp.pop() == Some(Value::from(10i64)));
+        assert!(comp.pop() == Some(Value::from(40i64)));
+        assert!(comp.pop() == Some(Value::from(10i64)));
+
+        comp.push_ops(["break"]);
+
+        assert!(matches!(
+            comp.evaluate_ops(),
+            Err(CompError::OutsideLoop { .. })
+        ));
+    }
+
+    #[test]
+    fn test_lexer() {
+        let tokens: Vec<Token> = lexer::lex("1 {note}2\n  \"a b\\n\\\"\" 'drop", "t.cm").unwrap();
+
+        assert!(tokens
+            .iter()
+            .map(|t| t.op.as_str())
+            .eq(["1", "2", "a b\n\"", "drop"]));
+        assert!(tokens[2].quoted && (tokens[2].line, tokens[2].column) == (2, 3));
+        assert!(tokens[2].source() == "\"a b\\n\\\"\"");
+        assert!(matches!(
+            lexer::lex("1 \"open", "t.cm"),
+            Err(CompError::Syntax { at, .. }) if at == "t.cm:1:3"
+        ));
+
+        let mut comp = Interpreter::new();
+        comp.push_source("( hi \"x y\" swap ) 'drop 7 hi", "t.cm")
+            .unwrap();
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.stack() == [Value::from("drop"), Value::from("x y"), Value::from(7i64)]);
+        assert!(comp.fns[0].fops == ["\"x y\"", "swap"]);
+    }
+
+    #[test]
+    fn test_parser() {
+        let ops: Vec<Token> = "( sq dup x ) 3 sq 4 ifeq 1 else { no } 0 fi"
+            .split_whitespace()
+            .map(Token::new)
+            .collect();
+        let nodes: Vec<Node> = parser::parse(ops).unwrap();
+
+        assert!(nodes.len() == 5);
+        assert!(
+            matches!(&nodes[0], Node::Function { name, fops, .. } if fops == &["dup", "x"] && name == "sq")
+        );
+        assert!(
+            matches!(&nodes[4], Node::If { then, otherwise, .. } if then.len() == 1 && otherwise.len() == 2)
+        );
+
+        let mut comp = Interpreter::new();
+
+        // deep recursion is not limited by the native stack
+        comp.push_ops("( down dup 0 gt if 1 - down fi ) 20000 down".split_whitespace());
+        comp.push_ops(
+            "( fib dup 2 lt if else dup 1 - fib swap 2 - fib + fi ) 20 fib".split_whitespace(),
+        );
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop() == Some(Value::from(6765i64)));
+        assert!(comp.pop() == Some(Value::from(0i64)));
+    }
+
+    #[test]
+    fn test_bytecode() {
+        let program: &str = "( stop dup 5 gt if break fi ) \
+            ( f 0 10 0 do idx 3 % 0 eq if continue fi idx + loop ) \
+            ( nest 0 3 times 2 times idx 10 x jdx + + loop loop ) ( jdx 0 ) \
+            ( h 1 begin dup 50 lt while 2 x repeat 0 begin 1 + stop repeat ) \
+            ( fact dup 1 ifeq drop 1 else dup 1 - fact x fi ) \
+            4 io [ dup x ] map f nest h 20 fact 3 times idx loop 5 2 do idx loop";
+
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops(program.split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0][..8] == [1, 4, 9, 16, 27, 30, 64, 6].map(|i: i64| Value::from(i)));
+        assert!(
+            stacks[0][8..] == [2432902008176640000, 0, 1, 2, 2, 3, 4].map(|i: i64| Value::from(i))
+        );
+
+        let mut comp = Interpreter::new();
+        comp.push_ops("( bad 3 times 1 + loop ) bad".split_whitespace());
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::StackUnderflow { .. })
+        ));
+        assert!(comp.loops.is_empty());
+
+        // memory and functions named like numbers are found in both modes
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops("( one 1 ) one 7 1 store one ( 2 3 ) ( two 2 ) two".split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0] == [1, 7, 3].map(|i: i64| Value::from(i)));
+    }
+
+    #[test]
+    fn test_locals() {
+        let program: &str = "( pow2 -> n ; n 0 ifeq 1 else n 1 - pow2 2 x fi ) \
+            ( sub -> a b ; a b - ) ( swapped -> x y ; y x ) 3 n store \
+            10 pow2 n 9 4 sub 1 2 swapped 2 io [ -> v ; v v + ] map";
+
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops(program.split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0] == [2048, 6, 10, 4, 2, 2, 4].map(|i: i64| Value::from(i)));
+
+        let mut comp = Interpreter::new();
+        comp.push_ops("1 2 -> a b ;".split_whitespace());
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::InvalidArgument { .. })
+        ));
+        assert!(comp.stack().len() == 2);
+    }
+
+    #[test]
+    fn test_quotations() {
+        let program: &str = "( twice -> q ; q call q call ) [ 1 + ] inc store [ 2 x ] dbl store \
+            1 dbl twice 5 inc dbl compose call 4 inc dip 6 [ 1 - ] keep 7 inc dbl bi \
+            2 [ x ] curry 3 swap call";
+
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops(program.split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0] == [4, 13, 4, 5, 6, 8, 14, 6].map(|i: i64| Value::from(i)));
+
+        let mut comp = Interpreter::new();
+        comp.push_source("1.5 \"a b\" [ dup x ] curry curry", "t.cm")
+            .unwrap();
+
+        comp.evaluate_ops().unwrap();
+
+        let quote: Value = comp.pop().unwrap();
+        assert!(quote.to_string() == "[ 1.5 \"a b\" dup x ]");
+
+        let yaml: String = serde_yaml::to_string(&quote).unwrap();
+        assert!(serde_yaml::from_str::<Value>(&yaml).unwrap() == quote);
+
+        comp.push_ops(["3", "call"]);
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::Parse { kind: "q", .. })
+        ));
+
+        // failing quotation leaves its call
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops("( id -> n ; n ) [ -> a ; a + ] 5 swap".split_whitespace());
+            comp.evaluate_ops().unwrap();
+
+            assert!(comp.evaluate_op("call").is_err());
+            assert!(comp.calls.is_empty());
+
+            comp.push_ops("7 id".split_whitespace());
+            comp.evaluate_ops().unwrap();
+
+            assert!(comp.pop() == Some(Value::from(7i64)));
+        }
+    }
+
+    #[test]
+    fn test_units() {
+        let program: &str = "9.81m/s^2 2kg x 3m x 1J / \
+            5km 500m + mi in 2 ^ sqrt \
+            60mph km/h in 1m 100cm eq \
+            5 m store m 500g kg in 90s h in";
+
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops(program.split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0][0] == Value::Float(58.86));
+        assert!(stacks[0][1].to_string() == "3.417541557305337 mi");
+        assert!(stacks[0][2].to_string() == "96.56064 km/h");
+        assert!(stacks[0][3] == Value::Bool(true));
+        assert!(stacks[0][4] == Value::from(5_i64));
+        assert!(stacks[0][5].to_string() == "0.5 kg");
+        assert!(stacks[0][6].to_string() == "0.025 h");
+
+        let yaml: String = serde_yaml::to_string(&stacks[0]).unwrap();
+        assert!(serde_yaml::from_str::<Vec<Value>>(&yaml).unwrap() == stacks[0]);
+
+        let mut comp = Interpreter::new();
+        comp.push_ops("3m 2s +".split_whitespace());
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::InvalidArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_dates() {
+        let program: &str = "2024-03-15T00:00:00Z 2024-01-01T00:00:00Z - d in \
+            2024-02-28T22:00:00Z 1d 3h + + 2024-03-01T01:00:00Z eq \
+            1710491400 unix_date date_unix 2024-03-15T10:30:00+02:00 dup weekday swap week \
+            90min hms 1:30:45 hms_dec";
+
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops(program.split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0][0].to_string() == "74 d");
+        assert!(stacks[0][1] == Value::Bool(true));
+        assert!(stacks[0][2] == Value::from(1710491400_i64));
+        assert!(stacks[0][3] == Value::from(5_i64));
+        assert!(stacks[0][4] == Value::from(11_i64));
+        assert!(stacks[0][5] == Value::from("1:30:00"));
+        assert!(stacks[0][6] == Value::Float(1.5125));
+
+        let date: Value = Value::parse("2024-03-15T10:30:00+02:00");
+        let yaml: String = serde_yaml::to_string(&date).unwrap();
+        assert!(serde_yaml::from_str::<Value>(&yaml).unwrap() == date);
+
+        let mut comp = Interpreter::new();
+        comp.push_ops("2024-03-15 2 +".split_whitespace());
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::InvalidArgument { .. })
+        ));
+
+        // hexadecimal operands are not durations
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops("1d hex_dec ( color 1d 2a 3b rgbh ) color".split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            let stack: Vec<Value> = comp.get_stack();
+            assert!(stack[0] == Value::from(29_i64));
+            assert!(stack[2].to_string().contains("#1d2a3b"));
+        }
+    }
+
+    #[test]
+    fn test_programmer() {
+        let program: &str =
+            "5 not 12 10 nand 12 10 nor -4 7 and 8 ws 5 not -6 twos 1 7 shl -128 1 sar -127 1 rol \
+            unsigned 5 not 200 1 sar 16 ws 4660 bswap 1 clz 8 ctz 5 0 btest 5 3 bset";
+
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops(program.split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        let expected: Vec<Value> = vec![
+            Value::from(-6_i64),
+            Value::from(-9_i64),
+            Value::from(-15_i64),
+            Value::from(4_i64),
+            Value::from(-6_i64),
+            Value::from("11111010"),
+            Value::from(-128_i64),
+            Value::from(-64_i64),
+            Value::from(3_i64),
+            Value::from(250_i64),
+            Value::from(228_i64),
+            Value::from(13330_i64),
+            Value::from(15_i64),
+            Value::from(3_i64),
+            Value::Bool(true),
+            Value::from(13_i64),
+        ];
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0] == expected);
+
+        let mut comp = Interpreter::new();
+        comp.push_ops("8 ws 1 8 bset".split_whitespace());
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::BadArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_radix() {
+        let program: &str = "0x1f 0o17 -0b1010 0xff_ff 255 16 base zz 36 base_dec";
+
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops(program.split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        let expected: Vec<Value> = vec![
+            Value::from(31_i64),
+            Value::from(15_i64),
+            Value::from(-10_i64),
+            Value::from(65535_i64),
+            Value::from("ff"),
+            Value::from(1295_i64),
+        ];
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0] == expected);
+
+        let mut comp = Interpreter::new();
+        comp.push_ops("16 radix 48879 -6 1234567".split_whitespace());
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.display_stack() == ["0xbeef", "-0x6", "0x12d687"]);
+
+        comp.config.word_size = 16;
+        comp.config.digit_grouping = true;
+        assert!(comp.display_stack() == ["0xbeef", "0xfffa", "0x12_d687"]);
+
+        comp.config.radix = 10;
+        assert!(comp.display_stack() == ["48_879", "-6", "1_234_567"]);
+
+        // prefixed literals are not read as prefixed by the radix commands
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops("0b1 hex_dec ( digits 0b1 16 base_dec ) digits".split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            assert!(comp.get_stack() == [Value::from(177_i64), Value::from(177_i64)]);
+        }
+
+        let mut comp = Interpreter::new();
+        comp.push_ops("255 37 base".split_whitespace());
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::BadArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_conversions() {
+        let toml: &str = "[conversions]\n\
+            usd_eur = { factor = 0.5 }\n\
+            c_k = { factor = 1, offset = 273 }\n\
+            swap = { factor = 2 }\n";
+
+        let mut comp = Interpreter::new();
+        comp.config = toml::from_str(toml).unwrap();
+        comp.register_conversions();
+        comp.push_ops("10 usd_eur 4 eur_usd 27 c_k 300 k_c".split_whitespace());
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.get_stack() == [5., 8., 300., 27.].map(Value::Float));
+        assert!(!comp.cmdmap.contains_key("swap_inv"));
+    }
+
+    #[test]
+    fn test_lists() {
+        let program: &str = "7 1 2 3 4 4 pack [ dup x ] map [ 1 - ] filter [ chs ] sort \
+            dup [ + ] 0 fold swap [ + ] scan 3 io 3 pack [ x ] zip unpack";
+
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops(program.split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0] == [7, 29, 16, 50, 87].map(|i: i64| Value::from(i)));
+
+        let mut comp = Interpreter::new();
+        comp.push_ops("5 3 pack".split_whitespace());
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::StackUnderflow { .. })
+        ));
+    }
+
+    #[test]
+    fn test_selection() {
+        let program: &str = "3 1 4 1 5 9 2 6 unique [ ] sort [ 4 lt ] partition \
+            [ 2 gte ] take_while swap [ 2 % ] group";
+
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops(program.split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        let list = |l: &[i64]| Value::List(l.iter().map(|i| Value::from(*i)).collect());
+
+        assert!(stacks[0] == stacks[1]);
+        assert!(
+            stacks[0]
+                == [
+                    list(&[4, 5, 6, 9]),
+                    Value::List(vec![list(&[1, 3]), list(&[2])]),
+                ]
+        );
+
+        let mut comp = Interpreter::new();
+        comp.push_ops("1 2 3 [ 5 gt ] find".split_whitespace());
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::InvalidArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_statistics() {
+        let run = |program: &str| -> Vec<f64> {
+            let mut comp = Interpreter::new();
+            comp.push_ops(program.split_whitespace());
+            comp.evaluate_ops().unwrap();
+            comp.get_stack()
+                .iter()
+                .map(|x| x.to_f64().unwrap())
+                .collect()
+        };
+
+        assert!(run("3 1 4 1 5 9 median") == [3.5]);
+        assert!(run("10 3 1 4 1 5 5 mediann") == [10., 3.]);
+        assert!(run("1 2 2 3 3 mode") == [2.]);
+        assert!(run("2 4 4 4 5 5 7 9 stdev") == [2.]);
+        assert!(run("1 2 3 4 5 svar") == [2.5]);
+        assert!(run("1 2 3 4 5 25 percentile") == [2.]);
+        assert!(run("1 2 3 4 5 quartiles") == [2., 3., 4.]);
+        assert!(run("7 9 1 range") == [8.]);
+        assert!(run("1 4 4 harmean") == [2.]);
+        assert!(run("1 3 2 5 3 7 linreg") == [2., 1.]);
+        assert!(run("0 1 1 2 2 0 corr") == [-0.5]);
+
+        let mut comp = Interpreter::new();
+        comp.push_ops("1 2 3 linreg".split_whitespace());
+
+        assert!(matches!(
+            comp.evaluate_ops().map_err(|error| error.cause().clone()),
+            Err(CompError::InvalidArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_trace() {
+        let source: &str = "( half 2 / )\n( pair + half\n  + )\n( run 3\n  pair 1 + )\n\n4 run";
+
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_source(source, "t.cm").unwrap();
+
+            let Err(CompError::Trace(error, trace)) = comp.evaluate_ops() else {
+                panic!("error has no trace");
+            };
+
+            assert!(matches!(*error, CompError::StackUnderflow { .. }));
+            assert!(
+                trace
+                    == [
+                        "in pair (t.cm:3)",
+                        "called from run (t.cm:5)",
+                        "called from t.cm:7"
+                    ]
+            );
+            assert!(comp.calls.is_empty());
+        }
+
+        let mut comp = Interpreter::new();
+        comp.push_source("1 2\n+ +", "t.cm").unwrap();
+
+        let Err(CompError::Trace(_, trace)) = comp.evaluate_ops() else {
+            panic!("error has no trace");
+        };
+
+        assert!(trace == ["at t.cm:2"]);
+
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops("( bad + ) ( outer 1 bad )".split_whitespace());
+            comp.evaluate_ops().unwrap();
+
+            assert!(comp.evaluate_op("outer").is_err());
+            assert!(comp.calls.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&1.to_string());
+        comp.push_op("+");
+
+        assert!(
+            comp.evaluate_ops()
+                == Err(CompError::StackUnderflow {
+                    op: String::from("+"),
+                    min_depth: 2,
+                })
+        );
+        assert!(
+            CompError::StackUnderflow {
+                op: String::from("+"),
+                min_depth: 2,
+            }
+            .to_string()
+                == "[+] operation called without at least 2 element(s) on stack"
+        );
+
+        comp.ops.clear();
+        comp.push_op(&0.to_string());
+        comp.push_op("io");
+
+        assert!(matches!(
+            comp.evaluate_ops(),
+            Err(CompError::InvalidArgument { .. })
+        ));
+
+        comp.ops.clear();
+        comp.push_op(&1.to_string());
+        comp.push_op(&1.to_string());
+        comp.push_op("ifeq");
+        comp.push_op("2");
+
+        assert!(matches!(
+            comp.evaluate_ops(),
+            Err(CompError::UnbalancedBlock { .. })
+        ));
+    }
+
+    #[test]
+    fn test_mem() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op("chs");
+        comp.push_op("abs");
+        comp.push_op("inv");
+        comp.push_op("inv");
+        comp.push_op("pi");
+        comp.push_op("e");
+        comp.push_op(&0.to_string());
+        comp.push_op("b");
+        comp.push_op("store");
+        comp.push_op("a");
+        comp.push_op("store");
+        comp.push_op("c");
+        comp.push_op("store");
+        comp.push_op("cls");
+        comp.push_op("b");
+        comp.push_op("c");
+        comp.push_op("+");
+        comp.push_op("a");
+        comp.push_op("+");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == std::f64::consts::PI + std::f64::consts::E);
+    }
+
+    #[test]
+    fn test_cmp() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&10.to_string());
+        comp.push_op("log");
+        comp.push_op("e");
+        comp.push_op("ln");
+        comp.push_op(&105.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op("%");
+        comp.push_op(&3049.to_string());
+        comp.push_op(&1009.to_string());
+        comp.push_op("gcd");
+        comp.push_op("prod");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == 1.);
+
+        comp.push_op(&20.to_string());
+        comp.push_op("!");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == 2432902008176640000.);
+
+        comp.push_op(&20.to_string());
+        comp.push_op("tng");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_i64().unwrap() == 210);
+    }
+
+    #[test]
+    fn test_rand() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&2.to_string());
+        comp.push_op("rand");
+        comp.push_op(&2.to_string());
+        comp.push_op("rand");
+        comp.push_op(&2.to_string());
+        comp.push_op("rand");
+        comp.push_op(&2.to_string());
+        comp.push_op("rand");
+        comp.push_op(&2.to_string());
+        comp.push_op("rand");
+        comp.push_op(&2.to_string());
+        comp.push_op("rand");
+        comp.push_op(&2.to_string());
+        comp.push_op("rand");
+        comp.push_op(&2.to_string());
+        comp.push_op("rand");
+        comp.push_op(&2.to_string());
+        comp.push_op("rand");
+        comp.push_op(&2.to_string());
+        comp.push_op("rand");
+        comp.push_op("max_all");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() <= 2.);
+    }
+
+    #[test]
+    fn test_minmax() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op("min");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == 1.);
+
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op("max");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == 2.);
+
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op("min_all");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == 1.);
+
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op("max_all");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == 4.);
+
+        comp.push_op(&(-1).to_string());
+        comp.push_op(&(-5).to_string());
+        comp.push_op(&(-10).to_string());
+        comp.push_op("minmax");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == -1.);
+        assert!(comp.pop_stack_f64().unwrap() == -10.);
+    }
+
+    #[test]
+    fn test_conv() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&100.to_string());
+        comp.push_op("c_f");
+        comp.push_op("f_c");
+        comp.push_op("dec_hex");
+        comp.push_op("hex_bin");
+        comp.push_op("bin_hex");
+        comp.push_op("hex_dec");
+        comp.push_op("dec_bin");
+        comp.push_op("bin_dec");
+        comp.push_op("ft_m");
+        comp.push_op("m_ft");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == 100.);
+    }
+
+    #[test]
+    fn test_literals() {
+        let program: &str = "2e5 hex_dec ( to_dec 1e2 hex_dec ) to_dec [ 2e5 hex_bin ] call \
+            2e5 dup hex_dec swap drop 1d dup hex_dec swap drop ( h hex_dec ) 1d h \
+            2e5 v store v hex_dec ( mid -> a b ; a hex_dec b hex_dec + 2 / ) 1e3 2 mid \
+            2e5 2 x";
+
+        let mut stacks: Vec<Vec<Value>> = vec![];
+        for compile in [true, false] {
+            let mut comp = Interpreter::new();
+            comp.config.compile = compile;
+            comp.push_ops(program.split_whitespace());
+
+            comp.evaluate_ops().unwrap();
+
+            stacks.push(comp.get_stack());
+        }
+
+        let expected: Vec<Value> = vec![
+            Value::from(741_i64),
+            Value::from(482_i64),
+            Value::from("1011100101"),
+            Value::from(741_i64),
+            Value::from(29_i64),
+            Value::from(29_i64),
+            Value::from(741_i64),
+            Value::parse("485/2"),
+            Value::Float(400000.),
+        ];
+        assert!(stacks[0] == stacks[1]);
+        assert!(stacks[0] == expected);
+        assert!(Value::parse("2e5") == Value::Float(200000.));
+        assert!(Value::parse("2e5").source() == "2e5");
+    }
+
+    #[test]
+    fn test_avg() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&(-2).to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op("avg");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == 0.);
+
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op("avg_all");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_f64().unwrap() == 2.5);
+    }
+
+    #[test]
+    fn test_misc() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&10.1.to_string());
+        comp.push_op("round");
+        comp.push_op(&10.1.to_string());
+        comp.push_op("floor");
+        comp.push_op(&10.1.to_string());
+        comp.push_op("ceil");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_u64().unwrap() == 11);
+        assert!(comp.pop_stack_u64().unwrap() == 10);
+        assert!(comp.pop_stack_u64().unwrap() == 10);
+
+        comp.push_op(&(-99).to_string());
+        comp.push_op("sgn");
+        comp.push_op(&109.to_string());
+        comp.push_op("sgn");
+        comp.push_op(&0.to_string());
+        comp.push_op("sgn");
+        comp.push_op("sum");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_i64().unwrap() == 0);
+
+        comp.push_op("cls");
+        comp.push_op(&28.to_string());
+        comp.push_op("divs");
+        comp.push_op("sum");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_i64().unwrap() == 28);
+    }
+
+    #[test]
+    fn test_stack() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op(&5.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op("rotn");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_i64().unwrap() == 3);
+
+        comp.push_op("cls");
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op(&5.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op("rolln");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_i64().unwrap() == 2);
+
+        comp.push_op("cls");
+        comp.push_op(&1.to_string());
+        comp.push_op(&2.to_string());
+        comp.push_op(&3.to_string());
+        comp.push_op(&4.to_string());
+        comp.push_op(&5.to_string());
+        comp.push_op("rev");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_i64().unwrap() == 1);
+
+        comp.push_op("rev");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_i64().unwrap() == 5);
+    }
+
+    #[test]
+    fn test_binary_ops() {
+        let mut comp = Interpreter::new();
+
+        comp.push_op(&10.to_string());
+        comp.push_op(&6.to_string());
+        comp.push_op("and");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_u64().unwrap() == 2);
+
+        comp.push_op(&10.to_string());
+        comp.push_op(&6.to_string());
+        comp.push_op("nand");
+        comp.push_op("not");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_u64().unwrap() == 2);
+
+        comp.push_op(&10.to_string());
+        comp.push_op(&6.to_string());
+        comp.push_op("or");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_u64().unwrap() == 14);
+
+        comp.push_op(&10.to_string());
+        comp.push_op(&6.to_string());
+        comp.push_op("nor");
+        comp.push_op("not");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_u64().unwrap() == 14);
+
+        comp.push_op(&10.to_string());
+        comp.push_op(&6.to_string());
+        comp.push_op("xor");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_u64().unwrap() == 12);
+
+        comp.push_op(&341.to_string());
+        comp.push_op("ones");
+
+        comp.evaluate_ops().unwrap();
+
+        assert!(comp.pop_stack_u64().unwrap() == 5);
+    }
+} // unit_test
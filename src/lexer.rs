@@ -0,0 +1,262 @@
+use crate::error::CompError;
+use std::fmt;
+use std::sync::Arc;
+
+/*
+
+    source text is split into tokens on whitespace. a few characters are
+    treated specially.
+
+      "hello world"    string literal ( may contain whitespace and escapes )
+      'drop            quoted symbol ( pushed without being evaluated )
+      { comment }      comment ( may nest and need not be surrounded by
+                       whitespace )
+
+    string escapes:  \n  \t  \r  \0  \\  \"  \'  \u{263a}
+
+*/
+
+/// Source operation and its position in the source text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub op: String,       // operation ( contents of string literals and quoted symbols )
+    pub quoted: bool,     // string literal or quoted symbol ( never evaluated )
+    pub origin: Arc<str>, // file ( or command line ) the operation was read from
+    pub line: usize,      // line number ( 0 if not read from source text )
+    pub column: usize,    // column number
+}
+
+/// File ( or command line ) and line of an operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loc {
+    pub origin: Arc<str>,
+    pub line: usize,
+}
+
+impl fmt::Display for Loc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.origin, self.line)
+    }
+}
+
+impl Token {
+    /// Unquoted operation that was not read from source text.
+    pub fn new(op: &str) -> Self {
+        Self {
+            op: op.to_string(),
+            quoted: false,
+            origin: Arc::from(""),
+            line: 0,
+            column: 0,
+        }
+    }
+
+    /// Location of the token ( none if it was not read from source text ).
+    pub fn loc(&self) -> Option<Loc> {
+        match self.line {
+            0 => None,
+            line => Some(Loc {
+                origin: self.origin.clone(),
+                line,
+            }),
+        }
+    }
+
+    /// Token as it would be written in source text.
+    pub fn source(&self) -> String {
+        match self.quoted {
+            true => quoted(&self.op),
+            false => self.op.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source())
+    }
+}
+
+/// Split source text into tokens. The origin names the file ( or command
+/// line ) the source text was read from.
+pub fn lex(source: &str, origin: &str) -> Result<Vec<Token>, CompError> {
+    Lexer {
+        origin: Arc::from(origin),
+        chars: source.chars().collect(),
+        pos: 0,
+        line: 1,
+        column: 1,
+    }
+    .tokens()
+}
+
+struct Lexer {
+    origin: Arc<str>,
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Lexer {
+    fn tokens(&mut self) -> Result<Vec<Token>, CompError> {
+        let mut tokens: Vec<Token> = vec![];
+
+        while let Some(c) = self.peek() {
+            let (line, column) = (self.line, self.column);
+            let origin: Arc<str> = self.origin.clone();
+            let token = |op: String, quoted: bool| Token {
+                op,
+                quoted,
+                origin,
+                line,
+                column,
+            };
+
+            match c {
+                _ if c.is_whitespace() => {
+                    self.next();
+                }
+                '{' => self.comment(),
+                '"' => {
+                    self.next();
+                    tokens.push(token(self.string(line, column)?, true));
+                }
+                '\'' => {
+                    self.next();
+                    let symbol: String = self.word();
+                    if symbol.is_empty() {
+                        return Err(self.error("quote is missing a symbol", line, column));
+                    }
+                    tokens.push(token(symbol, true));
+                }
+                _ => tokens.push(token(self.word(), false)),
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c: char = self.peek()?;
+        self.pos += 1;
+        match c {
+            '\n' => {
+                self.line += 1;
+                self.column = 1;
+            }
+            _ => self.column += 1,
+        }
+
+        Some(c)
+    }
+
+    // characters up to whitespace or the start of a comment
+    fn word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || c == '{' {
+                break;
+            }
+            word.push(c);
+            self.next();
+        }
+
+        word
+    }
+
+    // string literal contents up to the closing quote ( the opening quote at
+    // line and column has been read )
+    fn string(&mut self, line: usize, column: usize) -> Result<String, CompError> {
+        let mut s = String::new();
+        loop {
+            let (esc_line, esc_column) = (self.line, self.column);
+            match self.next() {
+                Some('"') => return Ok(s),
+                Some('\\') => s.push(self.escape(esc_line, esc_column)?),
+                Some(c) => s.push(c),
+                None => return Err(self.error("string is missing closing quote", line, column)),
+            }
+        }
+    }
+
+    // character of escape sequence ( the backslash at line and column has
+    // been read )
+    fn escape(&mut self, line: usize, column: usize) -> Result<char, CompError> {
+        let c: char = match self.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some(c @ ('\\' | '"' | '\'')) => c,
+            Some('u') if self.peek() == Some('{') => {
+                self.next();
+                let mut hex = String::new();
+                while let Some(c) = self.next() {
+                    if c == '}' {
+                        break;
+                    }
+                    hex.push(c);
+                }
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error("invalid unicode escape", line, column))?
+            }
+            _ => return Err(self.error("unknown escape sequence", line, column)),
+        };
+
+        Ok(c)
+    }
+
+    fn error(&self, reason: &str, line: usize, column: usize) -> CompError {
+        CompError::Syntax {
+            at: format!("{}:{line}:{column}", self.origin),
+            reason: reason.to_string(),
+        }
+    }
+
+    // skip comment ( comments nest and an unclosed comment runs to the end of
+    // the source text )
+    fn comment(&mut self) {
+        let mut depth: usize = 0;
+        while let Some(c) = self.next() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 1 => return,
+                '}' => depth -= 1,
+                _ => (),
+            }
+        }
+    }
+}
+
+/// String literal as it would be written in source text.
+pub fn quoted(s: &str) -> String {
+    format!("\"{}\"", escape(s))
+}
+
+// source form of string literal contents
+fn escape(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\0' => escaped.push_str("\\0"),
+            '\\' | '"' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
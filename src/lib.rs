@@ -0,0 +1,30 @@
+//! comp command interpreter library
+//!
+//! The interpreter behind the `comp` command line tool, available for use in
+//! other Rust programs. Operations are queued with
+//! [`Interpreter::push_ops`], evaluated with [`Interpreter::evaluate_ops`],
+//! and the resulting stack is read with [`Interpreter::stack`]. Additional
+//! native commands can be registered with [`Interpreter::build_native`].
+//!
+//! ```
+//! let mut comp = comp::Interpreter::new();
+//!
+//! comp.push_ops(["3", "dup", "x", "4", "dup", "x", "+"]);
+//! comp.evaluate_ops().unwrap();
+//!
+//! assert_eq!(comp.stack(), [comp::Value::from(25i64)]);
+//! ```
+
+mod bytecode;
+mod error;
+mod interpreter;
+mod lexer;
+mod parser;
+mod stats;
+mod units;
+mod value;
+
+pub use error::CompError;
+pub use interpreter::{Command, Config, Conversion, Interpreter};
+pub use units::Unit;
+pub use value::{Quote, Value};
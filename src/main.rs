@@ -1,10 +1,11 @@
 use colored::ColoredString;
+use comp::CompError;
 use std::path::Path;
 use std::process::exit;
 use std::{env, fs};
 
-mod comp;
 mod mona;
+mod repl;
 
 const RELEASE_STATE: &str = "c";
 
@@ -45,7 +46,13 @@ fn main() {
     let mut interpreter = comp::Interpreter::new();
 
     // get command arguments
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+
+    // skip prelude ( reproducible runs )
+    let skip_prelude: bool = args.iter().skip(1).any(|arg| arg == "--no-prelude");
+    if skip_prelude {
+        args.retain(|arg| arg != "--no-prelude");
+    }
 
     if args.len() > 1 {
         match args[1].as_str() {
@@ -70,25 +77,28 @@ fn main() {
                 let filename: String = args[2].to_string();
                 let path: &Path = Path::new(&filename);
 
-                let file_contents: String = match fs::read_to_string(&path) {
+                let file_contents: String = match fs::read_to_string(path) {
                     Ok(content) => content,
-                    Err(error) => {
-                        eprintln!(
-                            "  {}: could not read [{}]: {error}",
-                            theme.red_bold("error"),
-                            theme.blue_coffee_bold(&path.display().to_string()),
-                        );
-                        exit(exitcode::OSFILE);
-                    }
+                    Err(error) => exit_error(CompError::UnknownFile {
+                        path: path.display().to_string(),
+                        reason: error.to_string(),
+                    }),
                 };
-                // create operations list vector from file contents - split elements
-                let operations = file_contents.split_whitespace().map(|x| x.to_string());
+                // modules imported by the file are searched for in its directory
+                interpreter.set_script_path(path);
 
-                interpreter.ops.extend(operations);
+                // create operations list from file contents
+                if let Err(error) = interpreter.push_source(&file_contents, &filename) {
+                    exit_error(error);
+                }
 
                 // add additional operations from command line
                 if args.get(3).is_some() {
-                    interpreter.ops.extend((args[3..]).to_vec())
+                    if let Err(error) =
+                        interpreter.push_source(&args[3..].join(" "), "<command line>")
+                    {
+                        exit_error(error);
+                    }
                 }
             }
             "--help" | "help" => {
@@ -144,7 +154,10 @@ fn main() {
             }
             _ => {
                 // read expression (operations list) input from command line arguments
-                interpreter.ops = (args[1..]).to_vec();
+                if let Err(error) = interpreter.push_source(&args[1..].join(" "), "<command line>")
+                {
+                    exit_error(error);
+                }
             }
         };
     }
@@ -157,16 +170,30 @@ fn main() {
         interpreter.load_stack()
     }
 
-    // process operations list ( ops list was loaded into the interpreter
-    // in the match statement above based on command line arguments )
-    interpreter.evaluate_ops();
+    // load prelude functions
+    if !skip_prelude {
+        if let Err(error) = interpreter.load_prelude() {
+            exit_error(error);
+        }
+    }
 
-    /* display stack to user */
-    output_stack(
-        interpreter.get_stack(),
-        interpreter.config.show_stack_level,
-        interpreter.config.monochrome,
-    );
+    if args.len() > 1 {
+        // process operations list ( ops list was loaded into the interpreter
+        // in the match statement above based on command line arguments )
+        if let Err(error) = interpreter.evaluate_ops() {
+            exit_error(error);
+        }
+
+        /* display stack to user */
+        output_stack(
+            &interpreter.display_stack(),
+            interpreter.config.show_stack_level,
+            interpreter.config.monochrome,
+        );
+    } else {
+        // no arguments - start interactive session
+        repl::run(&mut interpreter);
+    }
 
     // save stack
     if interpreter.config.stack_persistence {
@@ -176,6 +203,39 @@ fn main() {
     exit(exitcode::OK);
 } // main
 
+// report interpreter error and exit with the corresponding exit code
+fn exit_error(error: CompError) -> ! {
+    // color theme
+    let theme = cor::Theme::new();
+
+    eprintln!("  {}: {}", theme.red_bold("error"), styled_error(&error));
+
+    let code: exitcode::ExitCode = match error.cause() {
+        CompError::StackUnderflow { .. }
+        | CompError::Parse { .. }
+        | CompError::BadArgument { .. }
+        | CompError::InvalidArgument { .. }
+        | CompError::UnbalancedBlock { .. }
+        | CompError::OutsideLoop { .. }
+        | CompError::Syntax { .. }
+        | CompError::Trace(..) => exitcode::USAGE,
+        CompError::UnknownFile { .. } => exitcode::OSFILE,
+    };
+
+    exit(code);
+}
+
+// interpreter error message with operands highlighted and calls dimmed
+fn styled_error(error: &CompError) -> String {
+    // color theme
+    let theme = cor::Theme::new();
+
+    error.styled(
+        &|operand| theme.blue_coffee_bold(operand).to_string(),
+        &|call| theme.grey_mouse(call).to_string(),
+    )
+}
+
 struct BoxedClosure<'a> {
     f: Box<dyn Fn(&str) -> ColoredString + 'a>,
 }
@@ -218,6 +278,11 @@ fn show_help() {
         theme.yellow_canary_bold("-f"),
         theme.blue_coffee_bold("<path>"),
     );
+    println!(
+        "    {}                      {}",
+        theme.grey_mouse("comp"),
+        theme.cream_bold("start interactive session"),
+    );
     println!();
     println!("{}", theme.cream_bold("OPTIONS"));
     println!(
@@ -240,6 +305,10 @@ fn show_help() {
         "        {}         show help information",
         theme.yellow_canary_bold("--help"),
     );
+    println!(
+        "        {}   do not load prelude file",
+        theme.yellow_canary_bold("--no-prelude"),
+    );
     println!();
     println!("{}", theme.cream_bold("DESCRIPTION"));
     println!(
@@ -302,7 +371,7 @@ fn show_version() {
     );
 }
 
-fn output_stack(stack: Vec<String>, annotate: bool, monochrome: bool) {
+fn output_stack(stack: &[String], annotate: bool, monochrome: bool) {
     // color theme
     let theme = cor::Theme::new();
 
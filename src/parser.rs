@@ -0,0 +1,280 @@
+use crate::error::CompError;
+use crate::lexer::{Loc, Token};
+use crate::value::Value;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/*
+
+    operations are parsed into a tree once, when the block that contains
+    them is reached. blocks ( function definitions, anonymous functions,
+    conditionals, loops, and comments ) become single nodes holding their
+    parsed bodies so that evaluation never rescans the operations list.
+
+      ( sq dup x ) 3 sq 4 ifeq 1 else 0 fi
+
+        Function { sq : [ dup x ] }
+        Op 3
+        Op sq
+        Op 4
+        If { ifeq : [ 1 ] else [ 0 ] }
+
+    local variables are bound with -> at the top of a function body. later
+    operations of the body ( but not of functions defined inside it ) that
+    name a local variable read it.
+
+      ( hyp -> a b ; a a x b b x + sqrt )
+
+        Function { hyp : [ Bind [ a b ] Local a Local a Op x .. ] }
+
+*/
+
+/// Parsed block body.
+pub type Block = Arc<[Node]>;
+
+/// Parsed operation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Op(String),     // command, function call, memory recall, or value
+    Quoted(String), // string literal or quoted symbol
+    Function {
+        name: String,
+        fops: Vec<String>, // function source operations
+        body: Block,
+    },
+    Lambda {
+        fops: Vec<String>, // function source operations
+        body: Block,
+    },
+    Comment,
+    If {
+        op: String, // if or ifeq
+        then: Block,
+        otherwise: Block,
+    },
+    Until(Block),        // begin .. until
+    While(Block, Block), // begin .. while .. repeat
+    Repeat(Block),       // begin .. repeat
+    Times(Block),        // n times .. loop
+    Do(Block),           // limit start do .. loop
+    Forget(String),      // forget name
+    Line(Loc),           // location of the operations that follow
+    Bind(Vec<String>),   // -> a b ; ( pop values into local variables )
+    Local(String),       // local variable
+    Push(Value),         // literal value ( of curried quotation )
+}
+
+// operations that open a block ( or take the next operation as a name )
+const OPENERS: &[&str] = &[
+    "(", "[", "{", "if", "ifeq", "begin", "times", "do", "forget", "->",
+];
+
+fn is_opener(op: &str) -> bool {
+    OPENERS.contains(&op)
+}
+
+/// Parse the block opened by op from the front of the operations list. The
+/// operations of the block are removed from the list.
+pub fn parse_block(op: &str, ops: &mut VecDeque<Token>) -> Result<Node, CompError> {
+    Parser::new(ops).block(op.to_string())
+}
+
+/// Parse a complete operations list.
+pub fn parse(ops: Vec<Token>) -> Result<Vec<Node>, CompError> {
+    let mut ops: VecDeque<Token> = ops.into();
+    let mut parser = Parser::new(&mut ops);
+
+    let mut nodes: Vec<Node> = vec![];
+    let mut at: Option<Loc> = None;
+    while let Some(token) = parser.ops.pop_front() {
+        parser.push_node(&mut nodes, &mut at, token)?;
+    }
+
+    Ok(nodes)
+}
+
+struct Parser<'a> {
+    ops: &'a mut VecDeque<Token>,
+    taken: Vec<Token>,   // operations removed from the list ( function source )
+    locals: Vec<String>, // local variables bound so far in the function body
+}
+
+impl<'a> Parser<'a> {
+    fn new(ops: &'a mut VecDeque<Token>) -> Self {
+        Self {
+            ops,
+            taken: vec![],
+            locals: vec![],
+        }
+    }
+
+    // next operation inside block opened by op ( error if block is not closed )
+    fn next(&mut self, op: &str, close: &str) -> Result<Token, CompError> {
+        let next: Token = self
+            .ops
+            .pop_front()
+            .ok_or_else(|| CompError::UnbalancedBlock {
+                op: op.to_string(),
+                close: close.to_string(),
+            })?;
+        self.taken.push(next.clone());
+
+        Ok(next)
+    }
+
+    fn node(&mut self, token: Token) -> Result<Node, CompError> {
+        match (token.quoted, is_opener(&token.op)) {
+            (true, _) => Ok(Node::Quoted(token.op)),
+            (false, true) => self.block(token.op),
+            (false, false) if self.locals.contains(&token.op) => Ok(Node::Local(token.op)),
+            (false, false) => Ok(Node::Op(token.op)),
+        }
+    }
+
+    fn block(&mut self, op: String) -> Result<Node, CompError> {
+        let node: Node = match op.as_str() {
+            "(" => {
+                let name: String = self.next(&op, ")")?.op;
+                let start: usize = self.taken.len();
+                let outer: Vec<String> = std::mem::take(&mut self.locals);
+                let (body, _) = self.body(&op, &[")"])?;
+                self.locals = outer;
+                Node::Function {
+                    name,
+                    fops: self.source(start),
+                    body,
+                }
+            }
+            "[" => {
+                let start: usize = self.taken.len();
+                let outer: Vec<String> = std::mem::take(&mut self.locals);
+                let (body, _) = self.body(&op, &["]"])?;
+                self.locals = outer;
+                Node::Lambda {
+                    fops: self.source(start),
+                    body,
+                }
+            }
+            "{" => {
+                self.comment();
+                Node::Comment
+            }
+            "if" | "ifeq" => {
+                let (then, end) = self.body(&op, &["else", "fi"])?;
+                let otherwise: Block = match end.as_str() {
+                    "else" => self.body(&op, &["fi"])?.0,
+                    _ => Block::default(),
+                };
+                Node::If {
+                    op,
+                    then,
+                    otherwise,
+                }
+            }
+            "begin" => match self.body(&op, &["while", "until", "repeat"])? {
+                (body, end) if end == "until" => Node::Until(body),
+                (body, end) if end == "while" => Node::While(body, self.body(&op, &["repeat"])?.0),
+                (body, _) => Node::Repeat(body),
+            },
+            "times" => Node::Times(self.body(&op, &["loop"])?.0),
+            "do" => Node::Do(self.body(&op, &["loop"])?.0),
+            "forget" => match self.ops.pop_front() {
+                Some(name) => {
+                    self.taken.push(name.clone());
+                    Node::Forget(name.op)
+                }
+                None => {
+                    return Err(CompError::InvalidArgument {
+                        op,
+                        reason: String::from("no name to forget"),
+                    })
+                }
+            },
+            "->" => {
+                let mut names: Vec<String> = vec![];
+                loop {
+                    let name: Token = self.next(&op, ";")?;
+                    match name.op.as_str() {
+                        ";" if !name.quoted => break,
+                        ")" | "]" if !name.quoted => {
+                            return Err(CompError::UnbalancedBlock {
+                                op,
+                                close: String::from(";"),
+                            })
+                        }
+                        _ => names.push(name.op),
+                    }
+                }
+                self.locals.extend(names.iter().cloned());
+                Node::Bind(names)
+            }
+            _ => Node::Op(op),
+        };
+
+        Ok(node)
+    }
+
+    // parse the body of the block opened by op up to the first of the closing
+    // operations that is not inside a nested block. the body and the closing
+    // operation are returned.
+    fn body(&mut self, op: &str, closes: &[&str]) -> Result<(Block, String), CompError> {
+        let mut body: Vec<Node> = vec![];
+        let mut at: Option<Loc> = None;
+
+        loop {
+            let next: Token = self.next(op, closes[closes.len() - 1])?;
+            if !next.quoted && closes.contains(&next.op.as_str()) {
+                return Ok((Block::from(body), next.op));
+            }
+            self.push_node(&mut body, &mut at, next)?;
+        }
+    }
+
+    // add node to body preceded by its location when the line has changed
+    // since the last location ( at ) in the body
+    fn push_node(
+        &mut self,
+        body: &mut Vec<Node>,
+        at: &mut Option<Loc>,
+        token: Token,
+    ) -> Result<(), CompError> {
+        if let Some(loc) = token.loc() {
+            if at.as_ref() != Some(&loc) {
+                body.push(Node::Line(loc.clone()));
+                *at = Some(loc);
+            }
+        }
+
+        let node: Node = self.node(token)?;
+        if !matches!(node, Node::Op(_) | Node::Quoted(_) | Node::Local(_)) {
+            *at = None; // nested blocks change the location
+        }
+        body.push(node);
+
+        Ok(())
+    }
+
+    // skip comment ( comments may only nest comments and an unclosed comment
+    // runs to the end of the operations list )
+    fn comment(&mut self) {
+        let mut nested: usize = 0;
+
+        while let Some(token) = self.ops.pop_front() {
+            self.taken.push(token.clone());
+            match token.op.as_str() {
+                "{" => nested += 1,
+                "}" if nested == 0 => return,
+                "}" => nested -= 1,
+                _ => (),
+            }
+        }
+    }
+
+    // operations taken since start ( excluding the closing operation )
+    fn source(&self, start: usize) -> Vec<String> {
+        self.taken[start..self.taken.len() - 1]
+            .iter()
+            .map(Token::source)
+            .collect()
+    }
+}
@@ -0,0 +1,157 @@
+use comp::Interpreter;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+static HISTORY_FILE: &str = ".comp_history";
+static PROMPT: &str = "comp> ";
+
+/*
+
+    interactive read-eval-print loop. a single interpreter is kept alive
+    for the session so the stack, memory, and user-defined functions
+    persist from line to line. the stack is redrawn after each line.
+
+*/
+
+pub fn run(interpreter: &mut Interpreter) {
+    // color theme
+    let theme = cor::Theme::new();
+
+    let mut editor: Editor<CompHelper, DefaultHistory> = match Editor::new() {
+        Ok(editor) => editor,
+        Err(error) => {
+            eprintln!(
+                "  {}: could not start interactive mode: {error}",
+                theme.red_bold("error"),
+            );
+            return;
+        }
+    };
+    editor.set_helper(Some(CompHelper::new(interpreter)));
+
+    let history_filename: String = history_path();
+    let _ = editor.load_history(&history_filename); // no history on first use
+
+    loop {
+        match editor.readline(PROMPT) {
+            Ok(line) => {
+                let line: &str = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let _ = editor.add_history_entry(line);
+
+                if matches!(line, "exit" | "quit") {
+                    break;
+                }
+
+                if let Err(error) = interpreter
+                    .push_source(line, "<interactive>")
+                    .and_then(|_| interpreter.evaluate_ops())
+                {
+                    // report error and discard rest of line - stack is kept
+                    eprintln!(
+                        "  {}: {}",
+                        theme.red_bold("error"),
+                        crate::styled_error(&error)
+                    );
+                    interpreter.clear_ops();
+                }
+
+                crate::output_stack(
+                    &interpreter.display_stack(),
+                    interpreter.config.show_stack_level,
+                    interpreter.config.monochrome,
+                );
+
+                // pick up any new user-defined functions and memory
+                if let Some(helper) = editor.helper_mut() {
+                    *helper = CompHelper::new(interpreter);
+                }
+            }
+            Err(ReadlineError::Interrupted) => continue, // ctrl-c clears line
+            Err(ReadlineError::Eof) => break,            // ctrl-d exits
+            Err(error) => {
+                eprintln!("  {}: {error}", theme.red_bold("error"));
+                break;
+            }
+        }
+    }
+
+    if let Err(error) = editor.save_history(&history_filename) {
+        eprintln!(
+            "  {}: history [{}] could not be saved: {error}",
+            theme.yellow_canary_bold("warning"),
+            theme.blue_smurf_bold(HISTORY_FILE),
+        );
+    }
+}
+
+// history file in home folder ( alongside stack snapshot and configuration )
+fn history_path() -> String {
+    let home_folder: String = match home::home_dir() {
+        Some(dir) => dir.to_str().unwrap().to_string(),
+        _ => String::from(""),
+    };
+
+    format!("{}/{}", home_folder, HISTORY_FILE)
+}
+
+// tab-completion of commands, user-defined functions, and memory names
+struct CompHelper {
+    names: Vec<String>,
+}
+
+impl CompHelper {
+    fn new(interpreter: &Interpreter) -> Self {
+        let mut names: Vec<String> = interpreter.get_cmds();
+        names.extend(interpreter.get_fns());
+        names.extend(interpreter.get_mem());
+        names.sort_unstable();
+        names.dedup();
+
+        Self { names }
+    }
+}
+
+impl Completer for CompHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        // start of word under cursor
+        let start: usize = line[..pos].rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let word: &str = &line[start..pos];
+
+        let candidates: Vec<Pair> = self
+            .names
+            .iter()
+            .filter(|name| name.starts_with(word))
+            .map(|name| Pair {
+                display: name.clone(),
+                replacement: name.clone(),
+            })
+            .collect();
+
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for CompHelper {
+    type Hint = String;
+}
+
+impl Highlighter for CompHelper {}
+
+impl Validator for CompHelper {}
+
+impl Helper for CompHelper {}
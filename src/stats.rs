@@ -0,0 +1,72 @@
+/*
+
+    statistics of samples. the statistics commands collect their samples from
+    the stack ( the whole stack or the top n elements ) and use the functions
+    below to compute the results. samples are never empty.
+
+      2 4 4 4 5 5 7 9 stdev
+
+        mean     5
+        variance 4  ( population )
+        stdev    2
+
+*/
+
+pub fn mean(xs: &[f64]) -> f64 {
+    xs.iter().sum::<f64>() / xs.len() as f64
+}
+
+// variance ( of the population, or of a sample when sample is set )
+pub fn variance(xs: &[f64], sample: bool) -> f64 {
+    let m: f64 = mean(xs);
+    let ss: f64 = xs.iter().map(|x| (x - m).powi(2)).sum();
+
+    match sample {
+        true => ss / (xs.len() - 1) as f64,
+        false => ss / xs.len() as f64,
+    }
+}
+
+// percentile p ( 0 to 100 ) of sorted samples ( linear interpolation between
+// the closest ranks )
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank: f64 = p / 100. * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lower] + (rank - lower as f64) * (sorted[upper] - sorted[lower])
+}
+
+pub fn geomean(xs: &[f64]) -> f64 {
+    mean(&xs.iter().map(|x| x.ln()).collect::<Vec<f64>>()).exp()
+}
+
+pub fn harmean(xs: &[f64]) -> f64 {
+    xs.len() as f64 / xs.iter().map(|x| x.recip()).sum::<f64>()
+}
+
+// sums of the products of the deviations from the means ( sxx, syy, sxy )
+fn deviations(xs: &[f64], ys: &[f64]) -> (f64, f64, f64) {
+    let (mx, my) = (mean(xs), mean(ys));
+
+    xs.iter()
+        .zip(ys)
+        .fold((0., 0., 0.), |(sxx, syy, sxy), (x, y)| {
+            let (dx, dy) = (x - mx, y - my);
+            (sxx + dx * dx, syy + dy * dy, sxy + dx * dy)
+        })
+}
+
+// pearson correlation coefficient
+pub fn corr(xs: &[f64], ys: &[f64]) -> f64 {
+    let (sxx, syy, sxy) = deviations(xs, ys);
+
+    sxy / (sxx * syy).sqrt()
+}
+
+// least squares line through the points ( slope, intercept )
+pub fn linreg(xs: &[f64], ys: &[f64]) -> (f64, f64) {
+    let (sxx, _, sxy) = deviations(xs, ys);
+    let slope: f64 = sxy / sxx;
+
+    (slope, mean(ys) - slope * mean(xs))
+}
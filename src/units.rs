@@ -0,0 +1,457 @@
+use crate::value::Value;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/*
+
+    a unit is a product of powers of unit symbols ( as written ). each symbol
+    is a unit of the table below, optionally preceded by a prefix. the
+    dimensions and the scale factor to base units of a unit are those of its
+    symbols combined.
+
+      kg*m/s^2
+
+        symbols     kg^1 m^1 s^-2
+        dimensions  length 1  mass 1  time -2
+        factor      1 ( kilograms, meters, and seconds are base units )
+
+    base units are m ( length ), kg ( mass ), s ( time ), K ( temperature ),
+    A ( current ), mol ( amount ), and bit ( data ). conversions between
+    units with the same dimensions are exact. temperature units also have an
+    offset from the base unit that is used when an absolute temperature is
+    converted.
+
+*/
+
+const DIMENSIONS: usize = 7;
+
+type Dims = [i32; DIMENSIONS];
+
+const NONE: Dims = [0, 0, 0, 0, 0, 0, 0];
+const LENGTH: Dims = [1, 0, 0, 0, 0, 0, 0];
+const MASS: Dims = [0, 1, 0, 0, 0, 0, 0];
+const TIME: Dims = [0, 0, 1, 0, 0, 0, 0];
+const TEMPERATURE: Dims = [0, 0, 0, 1, 0, 0, 0];
+const CURRENT: Dims = [0, 0, 0, 0, 1, 0, 0];
+const AMOUNT: Dims = [0, 0, 0, 0, 0, 1, 0];
+const DATA: Dims = [0, 0, 0, 0, 0, 0, 1];
+const AREA: Dims = [2, 0, 0, 0, 0, 0, 0];
+const VOLUME: Dims = [3, 0, 0, 0, 0, 0, 0];
+const FREQUENCY: Dims = [0, 0, -1, 0, 0, 0, 0];
+const SPEED: Dims = [1, 0, -1, 0, 0, 0, 0];
+const FORCE: Dims = [1, 1, -2, 0, 0, 0, 0];
+const PRESSURE: Dims = [-1, 1, -2, 0, 0, 0, 0];
+const ENERGY: Dims = [2, 1, -2, 0, 0, 0, 0];
+const POWER: Dims = [2, 1, -3, 0, 0, 0, 0];
+const CHARGE: Dims = [0, 0, 1, 0, 1, 0, 0];
+const VOLTAGE: Dims = [2, 1, -3, 0, -1, 0, 0];
+
+// prefixes a unit symbol accepts
+#[derive(PartialEq)]
+enum Prefixes {
+    None,
+    Metric, // SI prefixes ( k, M, m, u, .. )
+    Data,   // SI prefixes and binary prefixes ( Ki, Mi, .. )
+}
+
+struct Def {
+    symbol: &'static str,
+    dims: Dims,
+    factor: &'static str, // scale factor to base units
+    offset: &'static str, // offset from base unit ( temperatures )
+    prefixes: Prefixes,
+}
+
+const fn def(symbol: &'static str, dims: Dims, factor: &'static str, prefixes: Prefixes) -> Def {
+    Def {
+        symbol,
+        dims,
+        factor,
+        offset: "0",
+        prefixes,
+    }
+}
+
+const UNITS: &[Def] = &[
+    /* length */
+    def("m", LENGTH, "1", Prefixes::Metric),
+    def("inch", LENGTH, "0.0254", Prefixes::None),
+    def("ft", LENGTH, "0.3048", Prefixes::None),
+    def("yd", LENGTH, "0.9144", Prefixes::None),
+    def("mi", LENGTH, "1609.344", Prefixes::None),
+    def("nmi", LENGTH, "1852", Prefixes::None),
+    def("au", LENGTH, "149597870700", Prefixes::None),
+    def("ly", LENGTH, "9460730472580800", Prefixes::None),
+    /* area and volume */
+    def("ha", AREA, "10000", Prefixes::None),
+    def("acre", AREA, "4046.8564224", Prefixes::None),
+    def("L", VOLUME, "0.001", Prefixes::Metric),
+    def("gal", VOLUME, "0.003785411784", Prefixes::None),
+    def("qt", VOLUME, "0.000946352946", Prefixes::None),
+    def("pt", VOLUME, "0.000473176473", Prefixes::None),
+    def("floz", VOLUME, "0.0000295735295625", Prefixes::None),
+    /* mass */
+    def("g", MASS, "0.001", Prefixes::Metric),
+    def("t", MASS, "1000", Prefixes::None),
+    def("lb", MASS, "0.45359237", Prefixes::None),
+    def("oz", MASS, "0.028349523125", Prefixes::None),
+    def("st", MASS, "6.35029318", Prefixes::None),
+    /* time */
+    def("s", TIME, "1", Prefixes::Metric),
+    def("min", TIME, "60", Prefixes::None),
+    def("h", TIME, "3600", Prefixes::None),
+    def("d", TIME, "86400", Prefixes::None),
+    def("wk", TIME, "604800", Prefixes::None),
+    def("yr", TIME, "31557600", Prefixes::None), // julian year
+    def("Hz", FREQUENCY, "1", Prefixes::Metric),
+    /* speed */
+    def("mph", SPEED, "0.44704", Prefixes::None),
+    def("kn", SPEED, "1852/3600", Prefixes::None),
+    /* temperature */
+    def("K", TEMPERATURE, "1", Prefixes::None),
+    Def {
+        symbol: "degC",
+        dims: TEMPERATURE,
+        factor: "1",
+        offset: "273.15",
+        prefixes: Prefixes::None,
+    },
+    Def {
+        symbol: "degF",
+        dims: TEMPERATURE,
+        factor: "5/9",
+        offset: "45967/180",
+        prefixes: Prefixes::None,
+    },
+    def("degR", TEMPERATURE, "5/9", Prefixes::None),
+    /* force and pressure */
+    def("N", FORCE, "1", Prefixes::Metric),
+    def("lbf", FORCE, "4.4482216152605", Prefixes::None),
+    def("Pa", PRESSURE, "1", Prefixes::Metric),
+    def("bar", PRESSURE, "100000", Prefixes::Metric),
+    def("atm", PRESSURE, "101325", Prefixes::None),
+    def("psi", PRESSURE, "6894.757293168361", Prefixes::None),
+    def("mmHg", PRESSURE, "133.322387415", Prefixes::None),
+    def("torr", PRESSURE, "101325/760", Prefixes::None),
+    /* energy and power */
+    def("J", ENERGY, "1", Prefixes::Metric),
+    def("cal", ENERGY, "4.184", Prefixes::Metric),
+    def("Wh", ENERGY, "3600", Prefixes::Metric),
+    def("eV", ENERGY, "1.602176634e-19", Prefixes::Metric),
+    def("BTU", ENERGY, "1055.05585262", Prefixes::None),
+    def("W", POWER, "1", Prefixes::Metric),
+    def("hp", POWER, "745.69987158227022", Prefixes::None),
+    /* electricity and amount */
+    def("A", CURRENT, "1", Prefixes::Metric),
+    def("C", CHARGE, "1", Prefixes::Metric),
+    def("V", VOLTAGE, "1", Prefixes::Metric),
+    def("mol", AMOUNT, "1", Prefixes::Metric),
+    /* data */
+    def("bit", DATA, "1", Prefixes::Data),
+    def("B", DATA, "8", Prefixes::Data),
+];
+
+const PREFIXES: &[(&str, &str)] = &[
+    ("Q", "1e30"),
+    ("R", "1e27"),
+    ("Y", "1e24"),
+    ("Z", "1e21"),
+    ("E", "1e18"),
+    ("P", "1e15"),
+    ("T", "1e12"),
+    ("G", "1e9"),
+    ("M", "1e6"),
+    ("k", "1e3"),
+    ("h", "1e2"),
+    ("da", "1e1"),
+    ("d", "1e-1"),
+    ("c", "1e-2"),
+    ("m", "1e-3"),
+    ("u", "1e-6"),
+    ("µ", "1e-6"),
+    ("n", "1e-9"),
+    ("p", "1e-12"),
+    ("f", "1e-15"),
+    ("a", "1e-18"),
+];
+
+const BINARY_PREFIXES: &[(&str, u32)] = &[
+    ("Ki", 10),
+    ("Mi", 20),
+    ("Gi", 30),
+    ("Ti", 40),
+    ("Pi", 50),
+    ("Ei", 60),
+];
+
+/// Unit of a quantity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Unit {
+    symbols: Vec<(String, i32)>, // unit symbols and their powers
+    dims: Dims,
+    factor: BigRational, // scale factor to base units
+}
+
+impl Unit {
+    /// Parse a unit expression ( unit symbols with optional integer powers
+    /// separated by * and / ) such as `m`, `km/h`, or `kg*m/s^2`.
+    pub fn parse(s: &str) -> Option<Unit> {
+        let mut unit = Unit::none();
+
+        let mut sign: i32 = 1;
+        let mut rest: &str = s;
+        loop {
+            let end: usize = rest.find(['*', '/']).unwrap_or(rest.len());
+            let (symbol, power): (&str, i32) = match rest[..end].split_once('^') {
+                Some((symbol, power)) => (symbol, power.parse().ok()?),
+                None => (&rest[..end], 1),
+            };
+            match symbol {
+                "1" if power == 1 && unit.symbols.is_empty() => (), // 1/s
+                _ => unit = unit.mul(&Unit::symbol(symbol)?.pow(sign * power)),
+            }
+
+            match rest[end..].chars().next() {
+                Some(op) => {
+                    sign = if op == '/' { -1 } else { 1 };
+                    rest = &rest[end + 1..];
+                }
+                None => break,
+            }
+        }
+
+        match unit.symbols.is_empty() {
+            true => None,
+            false => Some(unit),
+        }
+    }
+
+    // unit without symbols
+    fn none() -> Unit {
+        Unit {
+            symbols: vec![],
+            dims: NONE,
+            factor: BigRational::one(),
+        }
+    }
+
+    // unit symbol ( optionally prefixed )
+    fn symbol(symbol: &str) -> Option<Unit> {
+        let (def, scale): (&Def, BigRational) = match lookup(symbol) {
+            Some(def) => (def, BigRational::one()),
+            None => prefixed(symbol)?,
+        };
+
+        Some(Unit {
+            symbols: vec![(symbol.to_string(), 1)],
+            dims: def.dims,
+            factor: exact(def.factor)? * scale,
+        })
+    }
+
+    /// Product of units ( powers of the same symbol are combined ).
+    pub fn mul(&self, other: &Unit) -> Unit {
+        let mut symbols: Vec<(String, i32)> = self.symbols.clone();
+        for (symbol, power) in &other.symbols {
+            match symbols.iter_mut().find(|(s, _)| s == symbol) {
+                Some((_, p)) => *p += power,
+                None => symbols.push((symbol.clone(), *power)),
+            }
+        }
+        symbols.retain(|(_, p)| *p != 0);
+
+        let mut dims: Dims = self.dims;
+        dims.iter_mut().zip(other.dims).for_each(|(d, o)| *d += o);
+
+        Unit {
+            symbols,
+            dims,
+            factor: &self.factor * &other.factor,
+        }
+    }
+
+    /// Unit raised to an integer power.
+    pub fn pow(&self, n: i32) -> Unit {
+        let mut unit: Unit = self.clone();
+        unit.symbols.iter_mut().for_each(|(_, p)| *p *= n);
+        unit.symbols.retain(|(_, p)| *p != 0);
+        unit.dims.iter_mut().for_each(|d| *d *= n);
+        unit.factor = self.factor.pow(n);
+
+        unit
+    }
+
+    /// Nth root of unit ( none if a power is not a multiple of n ).
+    pub fn root(&self, n: i32) -> Option<Unit> {
+        if self.symbols.iter().any(|(_, p)| p % n != 0) {
+            return None;
+        }
+
+        let mut unit: Unit = self.clone();
+        unit.symbols.iter_mut().for_each(|(_, p)| *p /= n);
+        unit.dims.iter_mut().for_each(|d| *d /= n);
+        unit.factor = self.root_factor(n)?;
+
+        Some(unit)
+    }
+
+    // nth root of the scale factor ( exact factors of the roots of the symbols )
+    fn root_factor(&self, n: i32) -> Option<BigRational> {
+        let mut factor: BigRational = BigRational::one();
+        for (symbol, power) in &self.symbols {
+            factor *= Unit::symbol(symbol)?.pow(power / n).factor;
+        }
+
+        Some(factor)
+    }
+
+    /// The unit measures the same kind of quantity as other.
+    pub fn compatible(&self, other: &Unit) -> bool {
+        self.dims == other.dims
+    }
+
+    /// The unit has no dimensions ( e.g. m/km ).
+    pub fn is_dimensionless(&self) -> bool {
+        self.dims == NONE
+    }
+
+    /// The unit measures time ( e.g. h or ms ).
+    pub fn is_time(&self) -> bool {
+        self.dims == TIME
+    }
+
+    /// Magnitude of a dimensionless quantity as a number without a unit.
+    pub fn scale(&self, magnitude: &Value) -> Option<Value> {
+        self.convert(magnitude, &Unit::none(), false)
+    }
+
+    // offset from the base unit of a unit of absolute temperature ( a single
+    // temperature symbol )
+    fn offset(&self) -> BigRational {
+        match self.symbols.as_slice() {
+            [(symbol, 1)] => lookup(symbol).and_then(|def| exact(def.offset)),
+            _ => None,
+        }
+        .unwrap_or_else(BigRational::zero)
+    }
+
+    /// Magnitude converted to another unit with the same dimensions ( none if
+    /// the magnitude is not a real number ). Offsets are applied when
+    /// absolute temperatures are converted. Exact magnitudes are converted
+    /// exactly and the result is an integer if it has no fractional part ( and
+    /// a float otherwise ).
+    pub fn convert(&self, magnitude: &Value, to: &Unit, absolute: bool) -> Option<Value> {
+        if self == to {
+            return Some(magnitude.clone());
+        }
+
+        let (from_offset, to_offset) = match absolute {
+            true => (self.offset(), to.offset()),
+            false => (BigRational::zero(), BigRational::zero()),
+        };
+
+        let exact: bool = magnitude.as_exact().is_some();
+        let x: BigRational = match magnitude.as_exact() {
+            Some(r) => r,
+            None => BigRational::from_f64(magnitude.as_f64()?)?,
+        };
+        let converted: BigRational = ((x * &self.factor + from_offset) - to_offset) / &to.factor;
+
+        match exact && converted.is_integer() {
+            true => Some(Value::Int(converted.to_integer())),
+            false => Some(Value::Float(converted.to_f64()?)),
+        }
+    }
+}
+
+// unit symbol definition
+fn lookup(symbol: &str) -> Option<&'static Def> {
+    UNITS.iter().find(|def| def.symbol == symbol)
+}
+
+// definition of prefixed unit symbol and the scale of the prefix
+fn prefixed(symbol: &str) -> Option<(&'static Def, BigRational)> {
+    for (prefix, scale) in PREFIXES {
+        if let Some(def) = symbol.strip_prefix(prefix).and_then(lookup) {
+            if def.prefixes != Prefixes::None {
+                return Some((def, exact(scale)?));
+            }
+        }
+    }
+    for (prefix, bits) in BINARY_PREFIXES {
+        if let Some(def) = symbol.strip_prefix(prefix).and_then(lookup) {
+            if def.prefixes == Prefixes::Data {
+                return Some((def, BigRational::from_integer(BigInt::one() << *bits)));
+            }
+        }
+    }
+
+    None
+}
+
+// exact value of a decimal ( e.g. 0.0254 or 1.6e-19 ) or fraction ( e.g. 5/9 )
+fn exact(s: &str) -> Option<BigRational> {
+    if let Some((numer, denom)) = s.split_once('/') {
+        return Some(exact(numer)? / exact(denom)?);
+    }
+
+    let (mantissa, exponent): (&str, i32) = match s.split_once('e') {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse().ok()?),
+        None => (s, 0),
+    };
+    let (whole, fraction): (&str, &str) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits: BigInt = format!("{whole}{fraction}").parse().ok()?;
+    let scale: i32 = exponent - fraction.len() as i32;
+
+    let ten: BigRational = BigRational::from_integer(BigInt::from(10));
+    Some(BigRational::from_integer(digits) * ten.pow(scale))
+}
+
+// symbols with positive powers are separated by * and followed by the symbols
+// with negative powers each preceded by / ( a unit with only negative powers
+// is written with negative exponents )
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let power = |symbol: &str, p: i32| match p {
+            1 => symbol.to_string(),
+            _ => format!("{symbol}^{p}"),
+        };
+
+        let numerator: Vec<String> = self
+            .symbols
+            .iter()
+            .filter(|(_, p)| *p > 0)
+            .map(|(s, p)| power(s, *p))
+            .collect();
+        if numerator.is_empty() {
+            let symbols: Vec<String> = self.symbols.iter().map(|(s, p)| power(s, *p)).collect();
+            return write!(f, "{}", symbols.join("*"));
+        }
+
+        write!(f, "{}", numerator.join("*"))?;
+        for (s, p) in self.symbols.iter().filter(|(_, p)| *p < 0) {
+            write!(f, "/{}", power(s, -p))?;
+        }
+
+        Ok(())
+    }
+}
+
+// units are persisted as unit expressions
+impl From<Unit> for String {
+    fn from(unit: Unit) -> Self {
+        unit.to_string()
+    }
+}
+
+impl TryFrom<String> for Unit {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Unit::parse(&s).ok_or(format!("invalid unit {s}"))
+    }
+}
@@ -0,0 +1,677 @@
+use crate::bytecode::{self, Code};
+use crate::error::CompError;
+use crate::interpreter::Command;
+use crate::lexer;
+use crate::parser::Block;
+use crate::units::Unit;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, Offset, TimeZone, Timelike};
+use num_bigint::{BigInt, BigUint};
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, ToPrimitive, Zero};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+// floats with a magnitude beyond this are never treated as integers
+const MAX_INTEGRAL_FLOAT: f64 = 1e38;
+
+/// Typed stack ( and memory ) value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Value {
+    #[serde(with = "decimal")]
+    Int(BigInt), // arbitrary precision
+    #[serde(with = "fraction")]
+    Rational(BigRational), // exact fraction ( never has a denominator of 1 )
+    Float(f64),
+    #[serde(with = "rectangular")]
+    Complex(Complex64), // never has an imaginary part of 0
+    Str(String), // strings and symbols
+    Bool(bool),
+    List(Vec<Value>),
+    #[serde(with = "quotation")]
+    Quote(Arc<Quote>), // anonymous function ( [ dup x ] )
+    Quantity(Box<Value>, Unit), // number with a unit ( 9.81m/s^2 )
+    #[serde(with = "iso8601")]
+    Date(DateTime<FixedOffset>), // date and time ( 2024-03-15T10:30:00 )
+    Literal(Box<Value>, String), // value with the digits it was written as ( 2e5 )
+}
+
+/// Quotation ( anonymous function that is pushed onto the stack and called
+/// by commands such as `call` and `map` ).
+#[derive(Debug)]
+pub struct Quote {
+    fops: Vec<String>,                // source operations
+    body: Block,                      // parsed operations
+    code: Mutex<Option<(u64, Code)>>, // compiled operations and command map version
+}
+
+impl Quote {
+    pub(crate) fn new(fops: Vec<String>, body: Block) -> Self {
+        Self {
+            fops,
+            body,
+            code: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn fops(&self) -> &[String] {
+        &self.fops
+    }
+
+    pub(crate) fn body(&self) -> &Block {
+        &self.body
+    }
+
+    // compiled operations ( compiled when first called and again when the
+    // version of the command map changes )
+    pub(crate) fn code(&self, cmdmap: &HashMap<String, Command>, version: u64) -> Code {
+        let mut code = self.code.lock().unwrap();
+        match &*code {
+            Some((compiled, code)) if *compiled == version => code.clone(),
+            _ => {
+                let compiled: Code = bytecode::compile(&self.body, cmdmap);
+                *code = Some((version, compiled.clone()));
+                compiled
+            }
+        }
+    }
+}
+
+impl Clone for Quote {
+    fn clone(&self) -> Self {
+        Self {
+            fops: self.fops.clone(),
+            body: self.body.clone(),
+            code: Mutex::new(self.code.lock().unwrap().clone()),
+        }
+    }
+}
+
+// quotations are equal if they have the same source
+impl PartialEq for Quote {
+    fn eq(&self, other: &Self) -> bool {
+        self.fops == other.fops
+    }
+}
+
+impl fmt::Display for Quote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.fops.is_empty() {
+            true => write!(f, "[ ]"),
+            false => write!(f, "[ {} ]", self.fops.join(" ")),
+        }
+    }
+}
+
+impl Value {
+    /// Parse an operation that is not a command into a value. Anything that
+    /// is not a number, quantity, date, or boolean is kept as a string.
+    pub fn parse(op: &str) -> Self {
+        let value: Value = parse_value(op);
+
+        // literals that read differently as digits ( 2e5 or 0b1 in hexadecimal )
+        // keep the text they were written as
+        if matches!(value, Value::Str(_)) || !op.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return value;
+        }
+        match value.digits().trim_start_matches('0') == op.trim_start_matches('0') {
+            true => value,
+            false => Value::Literal(Box::new(value), op.to_string()),
+        }
+    }
+
+    // value without the text of a literal
+    pub fn inner(&self) -> &Value {
+        match self {
+            Value::Literal(value, _) => value,
+            _ => self,
+        }
+    }
+
+    // owned value without the text of a literal
+    pub fn into_inner(self) -> Value {
+        match self {
+            Value::Literal(value, _) => *value,
+            _ => self,
+        }
+    }
+
+    // digits of the value as written ( read by the base conversion commands )
+    pub fn digits(&self) -> String {
+        match self {
+            Value::Literal(_, text) => text.clone(),
+            Value::Quantity(..) => self.source(),
+            _ => self.to_string(),
+        }
+    }
+
+    // integer value of integers and booleans ( floats are not converted )
+    pub fn as_exact_int(&self) -> Option<BigInt> {
+        match self.inner() {
+            Value::Int(i) => Some(i.clone()),
+            Value::Bool(b) => Some(BigInt::from(*b as u8)),
+            _ => None,
+        }
+    }
+
+    // exact value of integers, rationals, and booleans
+    pub fn as_exact(&self) -> Option<BigRational> {
+        match self.inner() {
+            Value::Rational(r) => Some(r.clone()),
+            _ => self.as_exact_int().map(BigRational::from_integer),
+        }
+    }
+
+    // integer value ( floats are converted only if they have no fractional part )
+    pub fn as_int(&self) -> Option<BigInt> {
+        match self.inner() {
+            Value::Float(f) if f.fract() == 0. && f.abs() < MAX_INTEGRAL_FLOAT => {
+                BigInt::from_f64(*f)
+            }
+            _ => self.as_exact_int(),
+        }
+    }
+
+    // floating point value of numeric values
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.inner() {
+            Value::Int(i) => i.to_f64(),
+            Value::Rational(r) => r.to_f64(),
+            Value::Float(f) => Some(*f),
+            Value::Bool(b) => Some(if *b { 1. } else { 0. }),
+            _ => None,
+        }
+    }
+
+    // complex value of numeric values
+    pub fn as_c64(&self) -> Option<Complex64> {
+        match self.inner() {
+            Value::Complex(c) => Some(*c),
+            _ => self.as_f64().map(Complex64::from),
+        }
+    }
+
+    // numeric ordering ( integers and rationals are compared exactly )
+    pub fn num_cmp(&self, other: &Value) -> Option<Ordering> {
+        match (self.as_exact(), other.as_exact()) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => self.as_f64()?.partial_cmp(&other.as_f64()?),
+        }
+    }
+
+    // truth value of booleans and numbers ( non-zero numbers are true )
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.inner() {
+            Value::Bool(b) => Some(*b),
+            Value::Complex(_) => Some(true),
+            _ => self.as_f64().map(|f| f != 0.),
+        }
+    }
+
+    // truth value ( error if value is not a boolean or number )
+    pub fn to_bool(&self) -> Result<bool, CompError> {
+        self.as_bool().ok_or_else(|| CompError::Parse {
+            value: self.to_string(),
+            kind: "b",
+        })
+    }
+
+    // list elements ( error if value is not a list )
+    pub fn to_list(self) -> Result<Vec<Value>, CompError> {
+        match self.into_inner() {
+            Value::List(l) => Ok(l),
+            value => Err(CompError::Parse {
+                value: value.to_string(),
+                kind: "l",
+            }),
+        }
+    }
+
+    // quotation ( error if value is not a quotation )
+    pub fn to_quote(&self) -> Result<Arc<Quote>, CompError> {
+        match self.inner() {
+            Value::Quote(q) => Ok(q.clone()),
+            _ => Err(CompError::Parse {
+                value: self.to_string(),
+                kind: "q",
+            }),
+        }
+    }
+
+    // value as it would be written in source text ( strings are quoted so
+    // that they are not evaluated )
+    pub fn source(&self) -> String {
+        match self {
+            Value::Str(s) => lexer::quoted(s),
+            Value::Literal(_, text) => text.clone(),
+            Value::Quantity(magnitude, unit) => format!("{}{unit}", magnitude.source()),
+            Value::Float(f) if Value::parse(&f.to_string()) != *self => format!("{f:e}"),
+            _ => self.to_string(),
+        }
+    }
+
+    // date ( error if value is not a date )
+    pub fn to_date(&self) -> Result<DateTime<FixedOffset>, CompError> {
+        match self.inner() {
+            Value::Date(d) => Ok(*d),
+            _ => Err(CompError::Parse {
+                value: self.to_string(),
+                kind: "d",
+            }),
+        }
+    }
+
+    // floating point value ( error if value is not numeric )
+    pub fn to_f64(&self) -> Result<f64, CompError> {
+        self.as_f64().ok_or_else(|| CompError::Parse {
+            value: self.to_string(),
+            kind: "f",
+        })
+    }
+
+    // complex value ( error if value is not numeric )
+    pub fn to_c64(&self) -> Result<Complex64, CompError> {
+        self.as_c64().ok_or_else(|| CompError::Parse {
+            value: self.to_string(),
+            kind: "c",
+        })
+    }
+
+    // integer value converted to the requested integer type ( error if value
+    // is not an integer or is out of range )
+    pub fn to_int<T: TryFrom<BigInt>>(&self, kind: &'static str) -> Result<T, CompError> {
+        self.as_int()
+            .and_then(|i| T::try_from(i).ok())
+            .ok_or_else(|| CompError::Parse {
+                value: self.to_string(),
+                kind,
+            })
+    }
+
+    // float result reduced to an integer when it has no fractional part
+    pub fn from_f64_int(f: f64) -> Self {
+        match f.fract() == 0. && f.abs() < MAX_INTEGRAL_FLOAT {
+            true => Value::Int(BigInt::from_f64(f).unwrap()),
+            false => Value::Float(f),
+        }
+    }
+}
+
+// number, quantity, date, boolean, or string
+fn parse_value(op: &str) -> Value {
+    if let Some(n) = parse_number(op) {
+        return n;
+    }
+    if let Some(q) = parse_quantity(op) {
+        return q;
+    }
+    if let Some(d) = parse_date(op) {
+        return Value::Date(d);
+    }
+    match op {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => Value::Str(op.to_string()),
+    }
+}
+
+// integer, fraction, float, or complex literal
+fn parse_number(op: &str) -> Option<Value> {
+    if let Some(i) = parse_prefixed(op) {
+        return Some(Value::Int(i));
+    }
+    if is_integer_literal(op) {
+        if let Ok(i) = op.parse::<BigInt>() {
+            return Some(Value::Int(i));
+        }
+    }
+    if let Some(r) = parse_fraction(op) {
+        return Some(Value::from(r));
+    }
+    if let Ok(f) = op.parse::<f64>() {
+        return Some(Value::Float(f));
+    }
+
+    parse_complex(op).map(Value::from)
+}
+
+// quantity literal is a number followed directly by a unit ( e.g. 5m,
+// 9.81m/s^2, or the durations 90s and 2d )
+fn parse_quantity(op: &str) -> Option<Value> {
+    if !op.starts_with(|c: char| c.is_ascii_digit() || "+-.".contains(c)) {
+        return None;
+    }
+
+    op.char_indices()
+        .filter(|&(i, c)| i > 0 && c.is_alphabetic())
+        .find_map(|(i, _)| {
+            let magnitude: Value = parse_number(&op[..i])?;
+            let unit: Unit = Unit::parse(&op[i..])?;
+            match magnitude {
+                Value::Complex(_) => None,
+                _ => Some(Value::Quantity(Box::new(magnitude), unit)),
+            }
+        })
+}
+
+// iso 8601 date literal ( e.g. 2024-03-15, 2024-03-15T10:30:00, or
+// 2024-03-15T10:30:00+02:00 ). dates without an offset are in local time
+fn parse_date(op: &str) -> Option<DateTime<FixedOffset>> {
+    let bytes: &[u8] = op.as_bytes();
+    if bytes.len() < 10 || !bytes[..4].iter().all(u8::is_ascii_digit) || bytes[4] != b'-' {
+        return None;
+    }
+    if let Ok(d) = DateTime::parse_from_rfc3339(op) {
+        return Some(d);
+    }
+
+    let naive: NaiveDateTime = match NaiveDate::parse_from_str(op, "%Y-%m-%d") {
+        Ok(date) => date.and_hms_opt(0, 0, 0)?,
+        Err(_) => ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(op, format).ok())?,
+    };
+
+    local_date(&naive)
+}
+
+// date from local time ( none if the time is skipped by a change of offset )
+pub(crate) fn local_date(naive: &NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+    Local
+        .from_local_datetime(naive)
+        .earliest()
+        .map(|d| d.fixed_offset())
+}
+
+// iso 8601 form of a date. the time is left out at midnight and the offset
+// is left out when it is the local offset
+fn format_date(d: &DateTime<FixedOffset>) -> String {
+    let local: FixedOffset = Local.offset_from_utc_datetime(&d.naive_utc()).fix();
+    let time: &str = match (d.time().num_seconds_from_midnight(), d.nanosecond()) {
+        (0, 0) if *d.offset() == local => "",
+        (_, 0) => "T%H:%M:%S",
+        _ => "T%H:%M:%S%.f",
+    };
+    let offset: &str = if *d.offset() == local { "" } else { "%:z" };
+
+    d.format(&format!("%Y-%m-%d{time}{offset}")).to_string()
+}
+
+// integer literal with a radix prefix ( e.g. 0x1f, 0o17, -0b1010, or
+// 0xffff_ffff )
+fn parse_prefixed(op: &str) -> Option<BigInt> {
+    let (sign, body): (&str, &str) = match op.strip_prefix('-') {
+        Some(body) => ("-", body),
+        None => ("", op.strip_prefix('+').unwrap_or(op)),
+    };
+    let radix: u32 = match body.get(..2)? {
+        "0x" => 16,
+        "0o" => 8,
+        "0b" => 2,
+        _ => return None,
+    };
+    let digits: &str = &body[2..];
+    if !digits.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    BigInt::parse_bytes(format!("{sign}{digits}").as_bytes(), radix)
+}
+
+// fraction literal ( e.g. 1/3 or -2/5 )
+fn parse_fraction(op: &str) -> Option<BigRational> {
+    let (numer, denom) = op.split_once('/')?;
+    if !is_integer_literal(numer) || !denom.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let denom: BigInt = denom.parse().ok()?;
+    if denom.is_zero() {
+        return None;
+    }
+
+    Some(BigRational::new(numer.parse().ok()?, denom))
+}
+
+// complex literal in rectangular form ( e.g. 3+4i, 1.5-2i, or -4i )
+fn parse_complex(op: &str) -> Option<Complex64> {
+    let body: &str = op.strip_suffix('i')?;
+    if body.is_empty()
+        || !body
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b))
+    {
+        return None;
+    }
+
+    // imaginary part starts at the last sign that is not part of an exponent
+    let split: Option<usize> = body
+        .char_indices()
+        .filter(|&(i, c)| i > 0 && (c == '+' || c == '-'))
+        .filter(|&(i, _)| !matches!(body.as_bytes()[i - 1], b'e' | b'E'))
+        .map(|(i, _)| i)
+        .next_back();
+
+    let (re, im): (f64, &str) = match split {
+        Some(i) => (body[..i].parse().ok()?, &body[i..]),
+        None => (0., body),
+    };
+    let im: f64 = match im {
+        "+" => 1.,
+        "-" => -1.,
+        _ => im.parse().ok()?,
+    };
+
+    Some(Complex64::new(re, im))
+}
+
+// optionally signed string of decimal digits ( the BigInt parser alone would
+// also accept digit separators )
+fn is_integer_literal(op: &str) -> bool {
+    let digits: &str = op.strip_prefix(['-', '+']).unwrap_or(op);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+// complex values are persisted as rectangular form strings
+mod rectangular {
+    use num_complex::Complex64;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(c: &Complex64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::Value::Complex(*c).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Complex64, D::Error> {
+        let s: String = String::deserialize(deserializer)?;
+        super::parse_complex(&s).ok_or_else(|| de::Error::custom("invalid complex number"))
+    }
+}
+
+// rationals are persisted as fraction strings
+mod fraction {
+    use num_rational::BigRational;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(r: &BigRational, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&r.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BigRational, D::Error> {
+        let s: String = String::deserialize(deserializer)?;
+        super::parse_fraction(&s).ok_or_else(|| de::Error::custom("invalid fraction"))
+    }
+}
+
+// quotations are persisted as source strings
+mod quotation {
+    use super::Quote;
+    use crate::interpreter::PERSISTENCE_FILE;
+    use crate::{lexer, parser};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::sync::Arc;
+
+    pub fn serialize<S: Serializer>(q: &Arc<Quote>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&q.fops.join(" "))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<Quote>, D::Error> {
+        let s: String = String::deserialize(deserializer)?;
+        let tokens: Vec<lexer::Token> =
+            lexer::lex(&s, PERSISTENCE_FILE).map_err(de::Error::custom)?;
+        let fops: Vec<String> = tokens.iter().map(lexer::Token::source).collect();
+        let body = parser::parse(tokens).map_err(de::Error::custom)?;
+
+        Ok(Arc::new(Quote::new(fops, body.into())))
+    }
+}
+
+// dates are persisted as rfc 3339 strings
+mod iso8601 {
+    use chrono::{DateTime, FixedOffset};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        d: &DateTime<FixedOffset>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&d.to_rfc3339())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<FixedOffset>, D::Error> {
+        let s: String = String::deserialize(deserializer)?;
+        DateTime::parse_from_rfc3339(&s).map_err(de::Error::custom)
+    }
+}
+
+// integers are persisted as decimal strings
+mod decimal {
+    use num_bigint::BigInt;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(i: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&i.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigInt, D::Error> {
+        let s: String = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(i) => write!(f, "{i}"),
+            Value::Rational(r) => write!(f, "{r}"),
+            Value::Float(x) => write!(f, "{x}"),
+            Value::Complex(c) => match c.im.is_sign_negative() {
+                true => write!(f, "{}-{}i", c.re, -c.im),
+                false => write!(f, "{}+{}i", c.re, c.im),
+            },
+            Value::Str(s) => write!(f, "{s}"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::List(l) => {
+                let items: Vec<String> = l.iter().map(|v| v.to_string()).collect();
+                write!(f, "({})", items.join(" "))
+            }
+            Value::Quote(q) => write!(f, "{q}"),
+            Value::Quantity(magnitude, unit) => write!(f, "{magnitude} {unit}"),
+            Value::Date(d) => write!(f, "{}", format_date(d)),
+            Value::Literal(value, _) => write!(f, "{value}"),
+        }
+    }
+}
+
+// literals are equal to the values they were parsed as
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Literal(a, _), b) | (b, Value::Literal(a, _)) => **a == *b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Rational(a), Value::Rational(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a == b,
+            (Value::Complex(a), Value::Complex(b)) => a == b,
+            (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::List(a), Value::List(b)) => a == b,
+            (Value::Quote(a), Value::Quote(b)) => a == b,
+            (Value::Quantity(a, x), Value::Quantity(b, y)) => a == b && x == y,
+            (Value::Date(a), Value::Date(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl From<BigInt> for Value {
+    fn from(i: BigInt) -> Self {
+        Value::Int(i)
+    }
+}
+
+// rationals with a denominator of 1 are reduced to integers
+impl From<BigRational> for Value {
+    fn from(r: BigRational) -> Self {
+        match r.is_integer() {
+            true => Value::Int(r.to_integer()),
+            false => Value::Rational(r),
+        }
+    }
+}
+
+// complex values with an imaginary part of 0 are reduced to floats
+impl From<Complex64> for Value {
+    fn from(c: Complex64) -> Self {
+        match c.im == 0. {
+            true => Value::Float(c.re),
+            false => Value::Complex(c),
+        }
+    }
+}
+
+impl From<BigUint> for Value {
+    fn from(i: BigUint) -> Self {
+        Value::Int(BigInt::from(i))
+    }
+}
+
+impl From<i64> for Value {
+    fn from(i: i64) -> Self {
+        Value::Int(BigInt::from(i))
+    }
+}
+
+impl From<u64> for Value {
+    fn from(i: u64) -> Self {
+        Value::Int(BigInt::from(i))
+    }
+}
+
+impl From<f64> for Value {
+    fn from(f: f64) -> Self {
+        Value::Float(f)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Str(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Str(s.to_string())
+    }
+}
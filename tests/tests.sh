@@ -4,4 +4,20 @@ comp -f ./tests/functions.cm
 comp -f ./tests/memory.cm
 comp -f ./tests/map.cm
 comp -f ./tests/fold.cm
-comp -f ./tests/scan.cm
\ No newline at end of file
+comp -f ./tests/scan.cm
+comp -f ./tests/locals.cm
+comp -f ./tests/lists.cm
+comp -f ./tests/filter.cm
+comp -f ./tests/take_while.cm
+comp -f ./tests/sort.cm
+comp -f ./tests/unique.cm
+comp -f ./tests/group.cm
+comp -f ./tests/count_if.cm
+comp -f ./tests/find.cm
+comp -f ./tests/partition.cm
+comp -f ./tests/statistics.cm
+comp -f ./tests/units.cm
+comp -f ./tests/dates.cm
+comp -f ./tests/programmer.cm
+comp -f ./tests/radix.cm
+comp -f ./tests/control.cm
\ No newline at end of file